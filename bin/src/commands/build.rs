@@ -3,7 +3,7 @@ use clap::{ArgAction, ArgMatches, Command};
 use crate::{
     error::Error,
     executor::Executor,
-    modules::{pbo::Collapse, Binarize, Files, Hooks, Lint, Rapifier},
+    modules::{pbo::Collapse, Binarize, Files, Hooks, Lint, Rapifier, SQFCompiler},
 };
 
 #[must_use]
@@ -31,6 +31,23 @@ pub fn add_args(cmd: Command) -> Command {
             .help("Do not rapify (cpp, rvmat)")
             .action(ArgAction::SetTrue),
     )
+    .arg(
+        clap::Arg::new("define")
+            .long("define")
+            .short('D')
+            .help("Define a preprocessor macro, as NAME or NAME=value")
+            .action(ArgAction::Append),
+    )
+}
+
+#[must_use]
+/// The defines passed with `-D`, as `NAME` or `NAME=value`
+pub fn defines(matches: &ArgMatches) -> Vec<String> {
+    matches
+        .get_many::<String>("define")
+        .unwrap_or_default()
+        .cloned()
+        .collect()
 }
 
 /// Execute the build command
@@ -48,6 +65,8 @@ pub fn execute(matches: &ArgMatches, executor: &mut Executor) -> Result<(), Erro
         executor.add_module(Box::<Binarize>::default());
     }
     executor.add_module(Box::<Hooks>::default());
+    // the native compiler leaves files it can not compile to the ArmaScriptCompiler
+    executor.add_module(Box::<SQFCompiler>::default());
     #[cfg(not(target_os = "macos"))]
     {
         executor.add_module(Box::<crate::modules::ArmaScriptCompiler>::default());
@@ -40,6 +40,13 @@ pub fn add_args(cmd: Command) -> Command {
             .help("Include all optional addon folders")
             .action(ArgAction::SetTrue),
     )
+    .arg(
+        clap::Arg::new("define")
+            .long("define")
+            .short('D')
+            .help("Define a preprocessor macro, as NAME or NAME=value")
+            .action(ArgAction::Append),
+    )
 }
 
 /// Execute the dev command
@@ -54,7 +61,12 @@ pub fn execute(matches: &ArgMatches, launch_optionals: &[String]) -> Result<Cont
         .map(std::string::String::as_str)
         .collect::<Vec<_>>();
 
-    let ctx = Context::new(std::env::current_dir()?, "dev")?.filter(|a, config| {
+    let ctx = Context::new(
+        std::env::current_dir()?,
+        "dev",
+        &super::build::defines(matches),
+    )?
+    .filter(|a, config| {
         if launch_optionals.iter().any(|o| o == a.name()) {
             return true;
         }
@@ -0,0 +1,115 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU16, Ordering},
+};
+
+use clap::{ArgAction, ArgMatches, Command};
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{context::Context, error::Error, modules::database};
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("fmt")
+        .about("Format the SQF files of the project")
+        .long_about("Format the SQF files of the project. Comments, preprocessor directives and the arguments of macros are kept as they are.")
+        .arg(
+            clap::Arg::new("paths")
+                .help("Files or folders to format, relative to the project root, defaults to every addon")
+                .action(ArgAction::Append),
+        )
+        .arg(
+            clap::Arg::new("check")
+                .long("check")
+                .help("Do not change any files, fail if a file is not formatted")
+                .action(ArgAction::SetTrue),
+        )
+}
+
+/// Execute the fmt command
+///
+/// # Errors
+/// [`Error::FormatFailed`] if a file could not be formatted
+/// [`Error::FormatCheckFailed`] if `--check` is used and a file is not formatted
+pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
+    let ctx = Context::read_only(std::env::current_dir()?, "fmt", &[])?;
+    let database = database(&ctx)?;
+    let check = matches.get_flag("check");
+    let roots = matches.get_many::<String>("paths").map_or_else(
+        || {
+            ctx.addons()
+                .iter()
+                .map(|addon| ctx.project_folder().join(addon.folder()))
+                .collect::<Vec<_>>()
+        },
+        |paths| {
+            paths
+                .map(|path| ctx.project_folder().join(path.replace('\\', "/")))
+                .collect()
+        },
+    );
+    let mut files = Vec::new();
+    for root in roots {
+        for entry in walkdir::WalkDir::new(root) {
+            let entry = entry?;
+            if entry.file_type().is_file()
+                && entry
+                    .path()
+                    .extension()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("sqf"))
+            {
+                files.push(entry.into_path());
+            }
+        }
+    }
+    let unformatted = AtomicU16::new(0);
+    let failed = files
+        .par_iter()
+        .map(|path| {
+            let name = relative(ctx.project_folder(), path);
+            let source = std::fs::read_to_string(path)?;
+            let formatted = match hemtt_sqf::format::format(&source, &database) {
+                Ok(formatted) => formatted,
+                Err(e) => {
+                    error!("{name}: {e}");
+                    return Ok(false);
+                }
+            };
+            if formatted != source {
+                unformatted.fetch_add(1, Ordering::Relaxed);
+                if check {
+                    warn!("{name} is not formatted");
+                } else {
+                    debug!("formatted {name}");
+                    std::fs::write(path, formatted)?;
+                }
+            }
+            Ok(true)
+        })
+        .collect::<Result<Vec<_>, Error>>()?
+        .into_iter()
+        .filter(|formatted| !formatted)
+        .count();
+    let unformatted = unformatted.load(Ordering::Relaxed);
+    if check {
+        info!("Checked {} files", files.len());
+    } else {
+        info!("Formatted {unformatted} of {} files", files.len());
+    }
+    if failed > 0 {
+        return Err(Error::FormatFailed);
+    }
+    if check && unformatted > 0 {
+        return Err(Error::FormatCheckFailed(unformatted));
+    }
+    Ok(())
+}
+
+/// The path of a file relative to the project, as it is written in the project config
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .map_or_else(|_| path.to_path_buf(), PathBuf::from)
+        .display()
+        .to_string()
+        .replace('\\', "/")
+}
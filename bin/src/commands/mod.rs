@@ -1,6 +1,8 @@
 pub mod build;
 pub mod dev;
+pub mod fmt;
 pub mod launch;
 pub mod new;
+pub mod preprocess;
 pub mod release;
 pub mod script;
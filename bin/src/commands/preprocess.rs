@@ -0,0 +1,287 @@
+use std::{fmt::Write, sync::Arc};
+
+use clap::{ArgAction, ArgMatches, Command};
+use hemtt_common::{
+    position::Position,
+    reporting::{Expansion, Output, Token},
+    workspace::WorkspacePath,
+};
+use hemtt_preprocessor::Processor;
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+use serde_json::{json, Value};
+
+use crate::{context::Context, error::Error, modules::preprocessed_files};
+
+use super::build;
+
+#[must_use]
+pub fn cli() -> Command {
+    Command::new("preprocess")
+        .about("Preprocess a single file")
+        .long_about("Preprocess a single file and print the output, or the macros that were expanded. Useful for finding out why a macro does not expand the way you expect.")
+        .arg(
+            clap::Arg::new("file")
+                .help("Path to the file, relative to the project root")
+                .required_unless_present("deps"),
+        )
+        .arg(
+            clap::Arg::new("tree")
+                .long("tree")
+                .help("Print the macros that were expanded, with where they were defined and their arguments")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("json")
+                .long("json")
+                .help("Print the expanded macros as JSON")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("lines")
+                .long("lines")
+                .help("Print the output with `#line` markers pointing to the original files")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            clap::Arg::new("deps")
+                .long("deps")
+                .help("Print the files included by the file, or by every file in the project if no file is given")
+                .value_parser(["makefile", "json", "dot"])
+                .action(ArgAction::Set),
+        )
+        .arg(
+            clap::Arg::new("define")
+                .long("define")
+                .short('D')
+                .help("Define a preprocessor macro, as NAME or NAME=value")
+                .action(ArgAction::Append),
+        )
+}
+
+/// Execute the preprocess command
+///
+/// # Errors
+/// [`Error`] if the file does not exist or can not be preprocessed
+///
+/// # Panics
+/// If a file is not provided without `--deps`, but this is usually handled by clap
+pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
+    // the output is printed, the build folder and CI annotations are left as they are
+    let ctx = Context::read_only(
+        std::env::current_dir()?,
+        "preprocess",
+        &build::defines(matches),
+    )?;
+    if let Some(format) = matches.get_one::<String>("deps") {
+        let files = match matches.get_one::<String>("file") {
+            Some(file) => vec![file_path(&ctx, file)?],
+            None => preprocessed_files(&ctx)?,
+        };
+        let graph = files
+            .par_iter()
+            .map(|path| {
+                let processed = Processor::run_cached(path, ctx.include_cache())?;
+                Ok(Node {
+                    file: path.clone(),
+                    dependencies: processed.dependencies().into_iter().cloned().collect(),
+                    includes: processed.includes().to_vec(),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        print!("{}", deps(format, &graph)?);
+        return Ok(());
+    }
+    let file = matches
+        .get_one::<String>("file")
+        .expect("file to be set as required");
+    let processed = Processor::run(&file_path(&ctx, file)?)?;
+    for warning in processed.warnings() {
+        if let Some(report) = warning.report_generate() {
+            eprintln!("{report}");
+        }
+    }
+    if matches.get_flag("json") {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&Value::Array(
+                processed.output().iter().filter_map(json).collect()
+            ))?
+        );
+    } else if matches.get_flag("tree") {
+        let mut tree = String::new();
+        for output in processed.output() {
+            write_tree(&mut tree, output, 0);
+        }
+        print!("{tree}");
+    } else if matches.get_flag("lines") {
+        print!("{}", processed.as_string_with_lines());
+    } else {
+        print!("{}", processed.as_string());
+    }
+    Ok(())
+}
+
+fn file_path(ctx: &Context, file: &str) -> Result<WorkspacePath, Error> {
+    let path = ctx.workspace().join(file.replace('\\', "/"))?;
+    if !path.is_file()? {
+        return Err(Error::PreprocessFileNotFound(file.to_owned()));
+    }
+    Ok(path)
+}
+
+/// A file in the include graph, with the files it includes
+struct Node {
+    file: WorkspacePath,
+    /// Every file that was included, once each
+    dependencies: Vec<WorkspacePath>,
+    /// Every `#include`, as (file, included file)
+    includes: Vec<(WorkspacePath, WorkspacePath)>,
+}
+
+/// The includes of each file, as a Makefile, JSON or DOT graph
+fn deps(format: &str, graph: &[Node]) -> Result<String, Error> {
+    let mut out = String::new();
+    match format {
+        "makefile" => {
+            // paths relative to the project root, with spaces escaped
+            let make =
+                |path: &WorkspacePath| path.as_str().trim_start_matches('/').replace(' ', "\\ ");
+            for entry in graph {
+                write!(out, "{}:", make(&entry.file)).expect("writing to a string");
+                for dependency in &entry.dependencies {
+                    write!(out, " {}", make(dependency)).expect("writing to a string");
+                }
+                writeln!(out).expect("writing to a string");
+            }
+        }
+        "json" => {
+            let files = graph
+                .iter()
+                .map(|entry| {
+                    json!({
+                        "file": entry.file.as_str(),
+                        "dependencies": entry
+                            .dependencies
+                            .iter()
+                            .map(WorkspacePath::as_str)
+                            .collect::<Vec<_>>(),
+                        "includes": entry
+                            .includes
+                            .iter()
+                            .map(|(from, to)| json!({ "from": from.as_str(), "to": to.as_str() }))
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            writeln!(
+                out,
+                "{}",
+                serde_json::to_string_pretty(&Value::Array(files))?
+            )
+            .expect("writing to a string");
+        }
+        "dot" => {
+            let quote =
+                |path: &WorkspacePath| format!("\"{}\"", path.as_str().replace('"', "\\\""));
+            let mut edges = Vec::new();
+            writeln!(out, "digraph includes {{").expect("writing to a string");
+            for entry in graph {
+                writeln!(out, "    {};", quote(&entry.file)).expect("writing to a string");
+                for (from, to) in &entry.includes {
+                    let edge = format!("{} -> {}", quote(from), quote(to));
+                    if !edges.contains(&edge) {
+                        edges.push(edge);
+                    }
+                }
+            }
+            for edge in edges {
+                writeln!(out, "    {edge};").expect("writing to a string");
+            }
+            writeln!(out, "}}").expect("writing to a string");
+        }
+        _ => unreachable!("clap only allows the listed formats"),
+    }
+    Ok(out)
+}
+
+/// `path:line:column` of a token, the column starts at 1
+fn location(position: &Position) -> String {
+    format!(
+        "{}:{}:{}",
+        position.path().as_str(),
+        position.start().line(),
+        position.start().column() + 1
+    )
+}
+
+fn tokens_to_string(tokens: &[Arc<Token>]) -> String {
+    tokens
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+fn arguments(expansion: &Expansion) -> Vec<String> {
+    expansion
+        .arguments
+        .iter()
+        .map(|arg| tokens_to_string(arg))
+        .collect()
+}
+
+/// The expanded output on a single line
+fn expanded(output: &Output) -> String {
+    output
+        .to_string()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn write_tree(tree: &mut String, output: &Output, depth: usize) {
+    let Output::Macro(token, children, expansion) = output else {
+        return;
+    };
+    let indent = "  ".repeat(depth);
+    let arguments = arguments(expansion);
+    let call = if arguments.is_empty() {
+        token.to_string()
+    } else {
+        format!("{}({})", token.to_string(), arguments.join(", "))
+    };
+    writeln!(tree, "{indent}{call} at {}", location(token.position()))
+        .expect("writing to a string");
+    writeln!(
+        tree,
+        "{indent}  defined at {}",
+        location(expansion.definition.position())
+    )
+    .expect("writing to a string");
+    writeln!(tree, "{indent}  = {}", expanded(output)).expect("writing to a string");
+    for child in children {
+        write_tree(tree, child, depth + 1);
+    }
+}
+
+fn json(output: &Output) -> Option<Value> {
+    let Output::Macro(token, children, expansion) = output else {
+        return None;
+    };
+    let position = |position: &Position| {
+        json!({
+            "path": position.path().as_str(),
+            "line": position.start().line(),
+            "column": position.start().column() + 1,
+        })
+    };
+    Some(json!({
+        "name": token.to_string(),
+        "callsite": position(token.position()),
+        "definition": position(expansion.definition.position()),
+        "arguments": arguments(expansion),
+        "output": output.to_string(),
+        "children": children.iter().filter_map(json).collect::<Vec<_>>(),
+    }))
+}
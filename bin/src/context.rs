@@ -1,11 +1,12 @@
 use std::{
     env::temp_dir,
     fs::{create_dir_all, remove_dir_all},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use hemtt_common::project::ProjectConfig;
 use hemtt_common::workspace::{Workspace, WorkspacePath};
+use hemtt_preprocessor::IncludeCache;
 
 use crate::{addons::Addon, error::Error};
 
@@ -20,6 +21,7 @@ pub struct Context {
     out_folder: PathBuf,
     build_folder: PathBuf,
     tmp: PathBuf,
+    include_cache: IncludeCache,
 }
 
 impl Context {
@@ -30,10 +32,36 @@ impl Context {
     /// [`Error::Io`] if the temporary folder fails to be created
     /// [`Error::Git`] if the git hash is invalid
     /// [`Error::Version`] if the version is invalid
+    /// [`Error::DefineInvalid`] if a define has an invalid name
+    /// [`Error::DefineBuiltin`] if a define would change a built-in macro
     ///
     /// # Panics
     /// If the project folder is not a valid [`OsStr`] (UTF-8)
-    pub fn new(root: PathBuf, folder: &str) -> Result<Self, Error> {
+    pub fn new(root: PathBuf, folder: &str, defines: &[String]) -> Result<Self, Error> {
+        let ctx = Self::read_only(root, folder, defines)?;
+        create_dir_all(&ctx.out_folder)?;
+        std::fs::File::create(ctx.out_folder.join("ci_annotation.txt"))?;
+        if ctx.build_folder.exists() {
+            remove_dir_all(&ctx.build_folder)?;
+        }
+        create_dir_all(&ctx.build_folder)?;
+        Ok(ctx)
+    }
+
+    /// Create a context for a command that does not write to `.hemttout`
+    ///
+    /// The build folder and CI annotations of the last build are left as they are
+    ///
+    /// # Errors
+    /// [`Error::ConfigNotFound`] if the project.toml is not found
+    /// [`Error::Git`] if the git hash is invalid
+    /// [`Error::Version`] if the version is invalid
+    /// [`Error::DefineInvalid`] if a define has an invalid name
+    /// [`Error::DefineBuiltin`] if a define would change a built-in macro
+    ///
+    /// # Panics
+    /// If the project folder is not a valid [`OsStr`] (UTF-8)
+    pub fn read_only(root: PathBuf, folder: &str, defines: &[String]) -> Result<Self, Error> {
         let config = {
             let path = root.join(".hemtt").join("project.toml");
             if !path.exists() {
@@ -41,6 +69,29 @@ impl Context {
             }
             ProjectConfig::from_file(&path)?
         };
+        let defines = {
+            // defines from the command line replace those from the project
+            let mut user = config.hemtt().defines().mode(folder);
+            for define in defines {
+                let (name, value) = define.split_once('=').unwrap_or((define, "1"));
+                user.push((name.trim().to_string(), value.to_string()));
+            }
+            for (name, _) in &user {
+                if name.is_empty()
+                    || name.starts_with(|c: char| c.is_ascii_digit())
+                    || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    return Err(Error::DefineInvalid(name.clone()));
+                }
+                if hemtt_preprocessor::is_builtin(name) {
+                    return Err(Error::DefineBuiltin(name.clone()));
+                }
+                trace!("using define: {name}");
+            }
+            let mut all = builtins(&config, &root)?;
+            all.extend(user);
+            all
+        };
         let tmp = {
             let mut tmp = temp_dir().join("hemtt");
             // on linux add the user to the path for multiple users
@@ -62,14 +113,8 @@ impl Context {
         trace!("using project folder: {:?}", root.display());
         let out_folder = root.join(".hemttout");
         trace!("using out folder: {:?}", out_folder.display());
-        create_dir_all(&out_folder)?;
-        std::fs::File::create(out_folder.join("ci_annotation.txt"))?;
         let build_folder = out_folder.join(folder);
         trace!("using build folder: {:?}", build_folder.display());
-        if build_folder.exists() {
-            remove_dir_all(&build_folder)?;
-        }
-        create_dir_all(&build_folder)?;
         let workspace = {
             let mut builder = Workspace::builder().physical(&root);
             if cfg!(target_os = "windows") {
@@ -79,7 +124,10 @@ impl Context {
             if include.is_dir() {
                 builder = builder.physical(&include);
             }
-            builder.memory().finish(Some(config.clone()))?
+            builder
+                .memory()
+                .defines(defines)
+                .finish(Some(config.clone()))?
         };
         {
             let version = config.version().get(workspace.vfs());
@@ -100,6 +148,7 @@ impl Context {
             build_folder,
             addons: Addon::scan()?,
             tmp,
+            include_cache: IncludeCache::new(),
         })
     }
 
@@ -173,4 +222,88 @@ impl Context {
     pub const fn tmp(&self) -> &PathBuf {
         &self.tmp
     }
+
+    #[must_use]
+    /// Tokenized include files, shared by every module
+    pub const fn include_cache(&self) -> &IncludeCache {
+        &self.include_cache
+    }
+}
+
+/// The built-in macros enabled by the project
+///
+/// The build time is taken from `SOURCE_DATE_EPOCH`, or the time of the HEAD commit,
+/// so the same commit always builds the same output. All times are UTC.
+fn builtins(config: &ProjectConfig, root: &Path) -> Result<Vec<(String, String)>, Error> {
+    let options = config.hemtt().builtins();
+    let mut defines = Vec::new();
+    if options.date() {
+        let epoch = if let Ok(epoch) = std::env::var("SOURCE_DATE_EPOCH") {
+            epoch.trim().parse::<i64>().map_err(|_| {
+                Error::BuildDateNotFound(format!("invalid SOURCE_DATE_EPOCH `{epoch}`"))
+            })?
+        } else {
+            git2::Repository::discover(root)
+                .and_then(|repo| Ok(repo.head()?.peel_to_commit()?.time().seconds()))
+                .map_err(|e| Error::BuildDateNotFound(e.message().to_string()))?
+        };
+        let time = time::OffsetDateTime::from_unix_timestamp(epoch)
+            .map_err(|e| Error::BuildDateNotFound(e.to_string()))?;
+        let clock = format!(
+            "{:02}:{:02}:{:02}",
+            time.hour(),
+            time.minute(),
+            time.second()
+        );
+        let date = format!(
+            "{}/{:02}/{:02}",
+            time.year(),
+            u8::from(time.month()),
+            time.day()
+        );
+        defines.extend([
+            (
+                "__DATE_ARR__".to_string(),
+                format!(
+                    "{},{},{},{},{},{}",
+                    time.year(),
+                    u8::from(time.month()),
+                    time.day(),
+                    time.hour(),
+                    time.minute(),
+                    time.second()
+                ),
+            ),
+            ("__DATE_STR__".to_string(), format!("\"{date}, {clock}\"")),
+            (
+                "__DATE_STR_ISO8601__".to_string(),
+                format!("\"{}T{clock}Z\"", date.replace('/', "-")),
+            ),
+            ("__TIME__".to_string(), clock.clone()),
+            ("__TIME_UTC__".to_string(), clock),
+            ("__TIMESTAMP_UTC__".to_string(), epoch.to_string()),
+        ]);
+    }
+    if let Some(version) = options.game_version() {
+        let parts = version.split('.').collect::<Vec<_>>();
+        let [major, minor, build] = parts.as_slice() else {
+            return Err(Error::GameVersionInvalid(version.to_string()));
+        };
+        if parts
+            .iter()
+            .any(|part| part.is_empty() || !part.chars().all(|c| c.is_ascii_digit()))
+        {
+            return Err(Error::GameVersionInvalid(version.to_string()));
+        }
+        defines.extend([
+            (
+                "__GAME_VER__".to_string(),
+                format!("{major:0>2}.{minor:0>2}.{build}"),
+            ),
+            ("__GAME_VER_MAJ__".to_string(), format!("{major:0>2}")),
+            ("__GAME_VER_MIN__".to_string(), format!("{minor:0>2}")),
+            ("__GAME_BUILD__".to_string(), (*build).to_string()),
+        ]);
+    }
+    Ok(defines)
 }
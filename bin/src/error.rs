@@ -23,14 +23,33 @@ pub enum Error {
     #[error("Addon prefix not found: {0}")]
     AddonPrefixMissing(String),
 
+    #[error("Invalid define name: {0}")]
+    DefineInvalid(String),
+    #[error("Define `{0}` would change a built-in macro")]
+    DefineBuiltin(String),
+    #[error(
+        "Build date not found for the date macros, set SOURCE_DATE_EPOCH or commit to git: {0}"
+    )]
+    BuildDateNotFound(String),
+    #[error("Invalid game version, expected `major.minor.build`: {0}")]
+    GameVersionInvalid(String),
+
     #[error("Hook signaled failure: {0}")]
     HookFatal(String),
     #[error("Script not found: {0}")]
     ScriptNotFound(String),
 
+    #[error("File to preprocess not found: {0}")]
+    PreprocessFileNotFound(String),
+
     #[error("One or more files failed linting")]
     LintFailed,
 
+    #[error("One or more files could not be formatted")]
+    FormatFailed,
+    #[error("{0} files are not formatted, run `hemtt fmt` to format them")]
+    FormatCheckFailed(u16),
+
     #[error("Unable to create link: {0}")]
     #[allow(dead_code)] // Unused on Linux and Mac
     Link(String),
@@ -53,6 +72,8 @@ pub enum Error {
     Prefix(#[from] hemtt_common::prefix::Error),
     #[error("`a hemtt project file is invalid: {0}")]
     Project(#[from] hemtt_common::project::Error),
+    #[error("SQF error: {0}")]
+    Sqf(#[from] hemtt_sqf::Error),
     #[error("Signing error: {0}")]
     Signing(#[from] hemtt_signing::Error),
     #[error("Version Error: {0}")]
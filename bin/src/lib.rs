@@ -41,6 +41,8 @@ pub fn cli() -> Command {
         .subcommand(commands::launch::cli())
         .subcommand(commands::release::cli())
         .subcommand(commands::script::cli())
+        .subcommand(commands::preprocess::cli())
+        .subcommand(commands::fmt::cli())
         .arg(
             clap::Arg::new("threads")
                 .global(true)
@@ -86,7 +88,10 @@ pub fn cli() -> Command {
 /// If the number passed to `--threads` is not a valid number
 pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
     if cfg!(not(debug_assertions)) || !matches.get_flag("in-test") {
-        logging::init(matches.get_count("verbosity"));
+        logging::init(
+            matches.get_count("verbosity"),
+            matches.subcommand_name() == Some("preprocess"),
+        );
     }
     if let Some(dir) = matches.get_one::<String>("dir") {
         std::env::set_current_dir(dir).expect("Failed to set current directory");
@@ -122,7 +127,11 @@ pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
             Ok(())
         }
         Some(("build", matches)) => {
-            let ctx = Context::new(std::env::current_dir()?, "build")?;
+            let ctx = Context::new(
+                std::env::current_dir()?,
+                "build",
+                &commands::build::defines(matches),
+            )?;
             let mut executor = executor::Executor::new(&ctx);
             commands::build::execute(matches, &mut executor).map_err(std::convert::Into::into)
         }
@@ -135,6 +144,10 @@ pub fn execute(matches: &ArgMatches) -> Result<(), Error> {
         Some(("script", matches)) => {
             commands::script::execute(matches).map_err(std::convert::Into::into)
         }
+        Some(("preprocess", matches)) => {
+            commands::preprocess::execute(matches).map_err(std::convert::Into::into)
+        }
+        Some(("fmt", matches)) => commands::fmt::execute(matches),
         _ => unreachable!(),
     }
 }
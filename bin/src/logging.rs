@@ -5,20 +5,29 @@ use std::{
 
 use tracing::metadata::LevelFilter;
 use tracing_subscriber::{
-    prelude::__tracing_subscriber_SubscriberExt, util::SubscriberInitExt, Layer,
+    fmt::writer::BoxMakeWriter, prelude::__tracing_subscriber_SubscriberExt,
+    util::SubscriberInitExt, Layer,
 };
 
 /// Initialize the logger
 ///
+/// Commands that print their result to stdout log to `stderr` instead
+///
 /// # Panics
 /// If the log file could not be created
-pub fn init(verbosity: u8) {
+pub fn init(verbosity: u8, stderr: bool) {
     let format = tracing_subscriber::fmt::format()
         .without_time()
         .with_target(false)
         .compact();
 
-    let stdout = tracing_subscriber::fmt::layer().event_format(format);
+    let stdout = tracing_subscriber::fmt::layer()
+        .event_format(format)
+        .with_writer(if stderr {
+            BoxMakeWriter::new(std::io::stderr)
+        } else {
+            BoxMakeWriter::new(std::io::stdout)
+        });
 
     let filter = if crate::is_ci() {
         LevelFilter::TRACE
@@ -31,11 +31,16 @@ It is always best to the include the log and a link to your project when reporti
 
     if let Err(e) = hemtt::execute(&hemtt::cli().get_matches()) {
         if let hemtt::Error::Preprocessor(e) = &e {
-            if let Some(code) = e.get_code() {
-                if let Some(report) = code.report_generate() {
+            let reports = e
+                .get_codes()
+                .into_iter()
+                .filter_map(hemtt_common::reporting::Code::report_generate)
+                .collect::<Vec<_>>();
+            if !reports.is_empty() {
+                for report in reports {
                     eprintln!("{report}");
-                    std::process::exit(1);
                 }
+                std::process::exit(1);
             }
         }
         error!("{}", e);
@@ -117,13 +117,22 @@ impl Module for ArmaScriptCompiler {
                         debug!("asc excluded {}", entry);
                         continue;
                     }
+                    if ctx.config().asc().native()
+                        && entry
+                            .parent()
+                            .join(format!("{}c", entry.filename()))?
+                            .exists()?
+                    {
+                        // already compiled by the native compiler
+                        continue;
+                    }
                     entries.push(entry);
                 }
             }
             entries
                 .par_iter()
                 .map(|entry| {
-                    let processed = Processor::run(entry)?;
+                    let processed = Processor::run_cached(entry, ctx.include_cache())?;
                     let source = tmp_addon.join(
                         entry
                             .as_str()
@@ -134,7 +143,11 @@ impl Module for ArmaScriptCompiler {
                         std::mem::drop(create_dir_all(parent));
                     }
                     let mut f = File::create(source)?;
-                    f.write_all(processed.as_string().as_bytes())?;
+                    if ctx.config().asc().line_markers() {
+                        f.write_all(processed.as_string_with_lines().as_bytes())?;
+                    } else {
+                        f.write_all(processed.as_string().as_bytes())?;
+                    }
                     files.write().unwrap().push((
                         format!(
                             "{}{}",
@@ -164,6 +177,9 @@ impl Module for ArmaScriptCompiler {
             "ASC Preprocess took {:?}",
             start.elapsed().whole_milliseconds()
         );
+        if ctx.config().asc().native() && files.read().unwrap().is_empty() {
+            return Ok(());
+        }
         for root in root_dirs {
             config.add_input_dir(root.to_string());
         }
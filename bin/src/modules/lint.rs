@@ -1,12 +1,27 @@
-use std::sync::atomic::{AtomicU16, Ordering};
+use std::{
+    fs::OpenOptions,
+    io::{BufWriter, Write},
+    sync::{
+        atomic::{AtomicU16, Ordering},
+        Mutex,
+    },
+};
 
-use hemtt_common::workspace::WorkspacePath;
-use hemtt_preprocessor::Processor;
+use hemtt_common::{
+    reporting::{Annotation, Code},
+    workspace::WorkspacePath,
+};
+use hemtt_preprocessor::{Processor, Unused};
+use hemtt_sqf::Functions;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::{context::Context, error::Error};
 
-use super::Module;
+use super::{
+    rapifier::{can_rapify, is_model_cfg},
+    sqf::database,
+    Module,
+};
 
 #[derive(Default)]
 pub struct Lint;
@@ -17,13 +32,18 @@ impl Module for Lint {
     }
 
     fn check(&self, ctx: &Context) -> Result<(), Error> {
-        if ctx.config().asc().enabled() {
-            return Ok(());
-        }
+        unused(ctx)?;
         if !ctx.config().lint().sqf().enabled() {
             return Ok(());
         }
+        functions(ctx)?;
+        let database = database(ctx)?;
         let counter = AtomicU16::new(0);
+        let mut ci_annotation = BufWriter::new(
+            OpenOptions::new()
+                .append(true)
+                .open(ctx.out_folder().join("ci_annotation.txt"))?,
+        );
         for addon in ctx.addons() {
             let sqf_ext = Some(String::from("sqf"));
             let mut entries = Vec::new();
@@ -48,21 +68,33 @@ impl Module for Lint {
             let entry_map = |entry: &WorkspacePath| {
                 debug!("linting {:?}", entry.as_str());
                 counter.fetch_add(1, Ordering::Relaxed);
-                match Processor::run(entry) {
-                    Err(e) => Err(e.into()),
-                    Ok(processed) => Ok(processed
-                        .warnings()
-                        .iter()
-                        .filter_map(|w| w.report_generate_processed(&processed))
-                        .collect::<Vec<String>>()),
+                let processed = Processor::run_cached(entry, ctx.include_cache())?;
+                let mut messages = processed
+                    .warnings()
+                    .iter()
+                    .filter_map(|w| w.report_generate().map(|report| (report, w.ci_generate())))
+                    .collect::<Vec<_>>();
+                // the lints need the parsed code, the compilers report if the file is invalid
+                if let Ok(statements) = hemtt_sqf::parse(&processed, &database) {
+                    messages.extend(
+                        hemtt_sqf::analyze(&statements, &database)
+                            .iter()
+                            .filter_map(|w| {
+                                w.report_generate_processed(&processed)
+                                    .map(|report| (report, w.ci_generate_processed(&processed)))
+                            }),
+                    );
+                } else {
+                    warn!("{} was not linted, it could not be parsed", entry);
                 }
+                Ok(messages)
             };
             let mut unique_warnings = Vec::new();
             let failed = entries
                 .par_iter()
                 .map(entry_map)
-                .collect::<Vec<Result<Vec<String>, Error>>>()
-                .iter()
+                .collect::<Vec<Result<Vec<(String, Vec<Annotation>)>, Error>>>()
+                .into_iter()
                 .filter(|r| {
                     match r {
                         Err(e) => {
@@ -81,8 +113,11 @@ impl Module for Lint {
                 })
                 .count()
                 > 0;
-            for warning in unique_warnings {
+            for (warning, annotations) in unique_warnings {
                 eprintln!("{warning}");
+                for annotation in annotations {
+                    ci_annotation.write_all(annotation.line().as_bytes())?;
+                }
             }
             if failed {
                 return Err(Error::LintFailed);
@@ -92,3 +127,117 @@ impl Module for Lint {
         Ok(())
     }
 }
+
+/// Every file in the project that is preprocessed during a build
+///
+/// # Errors
+/// [`Error`] if an addon folder can not be read
+pub fn preprocessed_files(ctx: &Context) -> Result<Vec<WorkspacePath>, Error> {
+    let mut files = Vec::new();
+    for addon in ctx.addons() {
+        for entry in ctx.workspace().join(addon.folder())?.walk_dir()? {
+            if entry.is_file()?
+                && (entry.extension().as_deref() == Some("sqf")
+                    || can_rapify(entry.as_str())
+                    || is_model_cfg(entry.as_str()))
+            {
+                files.push(entry);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Warn about macros and includes in the project that are never used, if enabled
+fn unused(ctx: &Context) -> Result<(), Error> {
+    let options = ctx.config().lint().preprocessor();
+    if !options.unused_macros() && !options.unused_includes() {
+        return Ok(());
+    }
+    let unused = Mutex::new(Unused::new());
+    preprocessed_files(ctx)?.par_iter().for_each(|entry| {
+        // files that fail to preprocess are reported by the other modules
+        if let Ok(processed) = Processor::run_cached(entry, ctx.include_cache()) {
+            unused.lock().expect("unused poisoned").add(&processed);
+        }
+    });
+    let unused = unused.into_inner().expect("unused poisoned");
+    let mut warnings = Vec::new();
+    if options.unused_macros() {
+        warnings.extend(unused.macros());
+    }
+    if options.unused_includes() {
+        warnings.extend(unused.includes());
+    }
+    report_warnings(ctx, warnings, options.exclude())
+}
+
+/// Warn about CBA functions that are referenced but do not exist, or prepared but never used
+fn functions(ctx: &Context) -> Result<(), Error> {
+    let mut functions = Functions::new();
+    let mut files = Vec::new();
+    for addon in ctx.addons() {
+        let mut entries = Vec::new();
+        for entry in ctx.workspace().join(addon.folder())?.walk_dir()? {
+            if entry.is_file()? {
+                // configs reference functions too, `QUOTE(call FUNC(name))`
+                if entry.extension().as_deref() == Some("sqf") || can_rapify(entry.as_str()) {
+                    files.push((addon.name().to_string(), entry.clone()));
+                }
+                entries.push(entry);
+            }
+        }
+        functions.add_addon(addon.name(), entries.iter().map(WorkspacePath::as_str));
+    }
+    let functions = Mutex::new(functions);
+    files.par_iter().for_each(|(addon, entry)| {
+        // files that fail to preprocess are reported by the other modules
+        if let Ok(processed) = Processor::run_cached(entry, ctx.include_cache()) {
+            functions
+                .lock()
+                .expect("functions poisoned")
+                .add(addon, &processed);
+        }
+    });
+    let functions = functions.into_inner().expect("functions poisoned");
+    let mut warnings = functions.missing();
+    warnings.extend(functions.unused());
+    report_warnings(ctx, warnings, ctx.config().lint().sqf().exclude())
+}
+
+/// Print warnings found across the project and write them to the CI annotations
+///
+/// Only warnings in files of the project are reported, not from include folders,
+/// and not in files matching `exclude`
+fn report_warnings(
+    ctx: &Context,
+    warnings: Vec<Box<dyn Code>>,
+    exclude: &[String],
+) -> Result<(), Error> {
+    let mut ci_annotation = BufWriter::new(
+        OpenOptions::new()
+            .append(true)
+            .open(ctx.out_folder().join("ci_annotation.txt"))?,
+    );
+    for warning in warnings {
+        let Some(token) = warning.token() else {
+            continue;
+        };
+        let path = token.position().path().as_str();
+        if !ctx
+            .addons()
+            .iter()
+            .any(|addon| path.starts_with(&format!("/{}/", addon.folder())))
+            || exclude.iter().any(|e| path.contains(e))
+        {
+            continue;
+        }
+        if let Some(report) = warning.report_generate() {
+            eprintln!("{report}");
+        }
+        for annotation in warning.ci_generate() {
+            ci_annotation.write_all(annotation.line().as_bytes())?;
+        }
+    }
+    Ok(())
+}
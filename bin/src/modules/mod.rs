@@ -15,16 +15,18 @@ mod lint;
 mod new;
 mod rapifier;
 mod sign;
+mod sqf;
 
 #[cfg(not(target_os = "macos"))]
 pub use asc::{ASCConfig, ArmaScriptCompiler};
 pub use binarize::Binarize;
 pub use file_patching::FilePatching;
 pub use files::Files;
-pub use lint::Lint;
+pub use lint::{preprocessed_files, Lint};
 pub use new::Licenses;
 pub use rapifier::Rapifier;
 pub use sign::Sign;
+pub use sqf::{database, SQFCompiler};
 
 pub trait Module {
     fn name(&self) -> &'static str;
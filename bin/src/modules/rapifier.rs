@@ -10,7 +10,7 @@ use hemtt_common::{
     reporting::{Annotation, Code},
     workspace::WorkspacePath,
 };
-use hemtt_config::{parse, rapify::Rapify};
+use hemtt_config::{parse_with_database, rapify::Rapify, ClassDatabase};
 use hemtt_preprocessor::Processor;
 use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
 use vfs::VfsFileType;
@@ -31,6 +31,16 @@ impl Module for Rapifier {
 
     fn pre_build(&self, ctx: &Context) -> Result<(), Error> {
         let counter = AtomicU16::new(0);
+        let database = {
+            let path = ctx.hemtt_folder().join("classes.json");
+            if path.exists() {
+                let database = ClassDatabase::from_json(&std::fs::read_to_string(path)?)?;
+                debug!("loaded {} classes from the class database", database.len());
+                Some(database)
+            } else {
+                None
+            }
+        };
         let glob_options = glob::MatchOptions {
             require_literal_separator: true,
             ..Default::default()
@@ -53,7 +63,7 @@ impl Module for Rapifier {
                 let mut res = Ok(());
                 for entry in ctx.workspace().join(addon.folder())?.walk_dir()? {
                     if entry.metadata()?.file_type == VfsFileType::File
-                        && can_rapify(entry.as_str())
+                        && (can_rapify(entry.as_str()) || is_model_cfg(entry.as_str()))
                     {
                         if globs
                             .iter()
@@ -63,9 +73,11 @@ impl Module for Rapifier {
                             continue;
                         }
                         debug!("rapifying {}", entry.as_str());
-                        let (new_messages, result) = rapify(entry.clone(), ctx);
+                        let (new_messages, result) = rapify(entry.clone(), ctx, database.as_ref());
                         messages.extend(new_messages);
-                        counter.fetch_add(1, Ordering::Relaxed);
+                        if can_rapify(entry.as_str()) {
+                            counter.fetch_add(1, Ordering::Relaxed);
+                        }
                         if let Err(e) = result {
                             res = Err(e);
                         }
@@ -95,8 +107,12 @@ impl Module for Rapifier {
     }
 }
 
-pub fn rapify(path: WorkspacePath, ctx: &Context) -> RapifyResult {
-    let processed = match Processor::run(&path) {
+pub fn rapify(
+    path: WorkspacePath,
+    ctx: &Context,
+    database: Option<&ClassDatabase>,
+) -> RapifyResult {
+    let processed = match Processor::run_cached(&path, ctx.include_cache()) {
         Ok(processed) => processed,
         Err(e) => {
             return (Vec::new(), Err(e.into()));
@@ -108,7 +124,9 @@ pub fn rapify(path: WorkspacePath, ctx: &Context) -> RapifyResult {
             messages.push((content, warning.ci_generate()));
         }
     }
-    let configreport = match parse(Some(ctx.config()), &processed) {
+    // rvmats, model.cfg and the like do not extend the game's config
+    let database = database.filter(|_| path.filename().eq_ignore_ascii_case("config.cpp"));
+    let configreport = match parse_with_database(Some(ctx.config()), &processed, database) {
         Ok(configreport) => configreport,
         Err(errors) => {
             for e in &errors {
@@ -171,6 +189,10 @@ pub fn rapify(path: WorkspacePath, ctx: &Context) -> RapifyResult {
             ))),
         );
     }
+    if is_model_cfg(path.as_str()) {
+        // model.cfg is used by binarize as is, it is only analyzed
+        return (messages, Ok(()));
+    }
     let out = if path.filename().to_lowercase() == "config.cpp" {
         path.parent().join("config.bin").unwrap()
     } else {
@@ -188,6 +210,11 @@ pub fn rapify(path: WorkspacePath, ctx: &Context) -> RapifyResult {
     (messages, Ok(()))
 }
 
+#[must_use]
+/// Check if a file is rapified during a build
+///
+/// # Panics
+/// If the extension is not valid UTF-8
 pub fn can_rapify(path: &str) -> bool {
     let path = PathBuf::from(path);
     let name = path
@@ -197,3 +224,11 @@ pub fn can_rapify(path: &str) -> bool {
         .unwrap();
     ["cpp", "rvmat", "ext"].contains(&name)
 }
+
+#[must_use]
+/// Check if a file is a `model.cfg`
+pub fn is_model_cfg(path: &str) -> bool {
+    PathBuf::from(path)
+        .file_name()
+        .is_some_and(|name| name.eq_ignore_ascii_case("model.cfg"))
+}
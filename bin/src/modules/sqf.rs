@@ -0,0 +1,142 @@
+use std::{
+    io::Write,
+    sync::atomic::{AtomicU16, Ordering},
+};
+
+use hemtt_common::{reporting::Processed, workspace::WorkspacePath};
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::{commands::Database, compiler::compile, Statements};
+use rayon::prelude::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::{context::Context, error::Error};
+
+use super::Module;
+
+#[derive(Default)]
+/// Compiles SQF to `.sqfc` without the `ArmaScriptCompiler` binary, enabled by `asc.native`
+pub struct SQFCompiler;
+
+impl Module for SQFCompiler {
+    fn name(&self) -> &'static str {
+        "SQFCompiler"
+    }
+
+    fn pre_build(&self, ctx: &Context) -> Result<(), Error> {
+        if !ctx.config().asc().enabled() || !ctx.config().asc().native() {
+            return Ok(());
+        }
+        let sqf_ext = Some(String::from("sqf"));
+        let mut entries = Vec::new();
+        for addon in ctx.addons() {
+            for entry in ctx.workspace().join(addon.folder())?.walk_dir()? {
+                if !entry.is_file()? || entry.extension() != sqf_ext {
+                    continue;
+                }
+                if ctx.config().asc().exclude().iter().any(|e| {
+                    entry
+                        .as_str()
+                        .to_ascii_lowercase()
+                        .contains(&e.to_ascii_lowercase())
+                }) {
+                    debug!("sqf compile excluded {}", entry);
+                    continue;
+                }
+                entries.push(entry);
+            }
+        }
+        let database = database(ctx)?;
+        let counter = AtomicU16::new(0);
+        entries
+            .par_iter()
+            .map(|entry| {
+                if compile_file(entry, ctx, &database)? {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+                Ok(())
+            })
+            .collect::<Result<(), Error>>()?;
+        info!("Compiled {} sqf files", counter.load(Ordering::Relaxed));
+        Ok(())
+    }
+}
+
+/// The commands of the game, and the project's own from `.hemtt/commands.txt`
+///
+/// # Errors
+/// [`Error`] if `.hemtt/commands.txt` can not be read, or is invalid
+pub fn database(ctx: &Context) -> Result<Database, Error> {
+    let path = ctx.hemtt_folder().join("commands.txt");
+    if !path.exists() {
+        return Ok(Database::builtin().clone());
+    }
+    let database = Database::with_definitions(&std::fs::read_to_string(path)?)?;
+    debug!("loaded command definitions from the project");
+    Ok(database)
+}
+
+/// Compile a file to `.sqfc` next to it, returns if there was any code to compile
+fn compile_file(path: &WorkspacePath, ctx: &Context, database: &Database) -> Result<bool, Error> {
+    let processed = Processor::run_cached(path, ctx.include_cache())?;
+    let statements = match hemtt_sqf::parse(&processed, database) {
+        Ok(statements) => statements,
+        Err(errors) => {
+            // the list of commands is not complete, the file can still be valid for the game
+            for error in errors {
+                debug!("{}: {}", path, error.message());
+            }
+            if cfg!(target_os = "macos") {
+                warn!(
+                    "{} could not be parsed, it is packed without compiling",
+                    path
+                );
+            } else {
+                warn!(
+                    "{} could not be parsed, it is left to the ArmaScriptCompiler",
+                    path
+                );
+            }
+            return Ok(false);
+        }
+    };
+    if statements.content().is_empty() {
+        // nothing to compile, the same as the ArmaScriptCompiler
+        return Ok(false);
+    }
+    if let Err(e) = write(path, &statements, &processed, ctx) {
+        error!("unable to compile {}", path);
+        return Err(e);
+    }
+    Ok(true)
+}
+
+fn write(
+    path: &WorkspacePath,
+    statements: &Statements,
+    processed: &Processed,
+    ctx: &Context,
+) -> Result<(), Error> {
+    let mut output = Vec::new();
+    compile(statements, processed, |path| location(ctx, path)).serialize(&mut output)?;
+    path.parent()
+        .join(format!("{}c", path.filename()))?
+        .create_file()?
+        .write_all(&output)?;
+    Ok(())
+}
+
+/// The path of a workspace file with the prefix of its addon, the same as given to the `ArmaScriptCompiler`
+fn location(ctx: &Context, path: &str) -> String {
+    let path = path.trim_start_matches('/');
+    ctx.addons()
+        .iter()
+        .find_map(|addon| {
+            path.strip_prefix(&format!("{}/", addon.folder()))
+                .map(|relative| {
+                    format!(
+                        "{}/{relative}",
+                        addon.prefix().to_string().replace('\\', "/")
+                    )
+                })
+        })
+        .unwrap_or_else(|| path.to_string())
+}
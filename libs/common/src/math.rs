@@ -1,14 +1,29 @@
 //! Math utilities
 
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, hash::BuildHasher};
 
 #[must_use]
 /// Evaluates a mathematical expression
 pub fn eval(expression: &str) -> Option<f64> {
-    evaluate_postfix(&shunting_yard(expression)?)
+    eval_with_variables(expression, &HashMap::new())
 }
 
-fn shunting_yard(expression: &str) -> Option<Vec<Token>> {
+#[must_use]
+/// Evaluates a mathematical expression that may contain variables
+///
+/// Variable names are case insensitive, `variables` must use lowercase keys.
+/// Returns `None` if a variable is not defined
+pub fn eval_with_variables<S: BuildHasher>(
+    expression: &str,
+    variables: &HashMap<String, f64, S>,
+) -> Option<f64> {
+    evaluate_postfix(&shunting_yard(expression, variables)?)
+}
+
+fn shunting_yard<S: BuildHasher>(
+    expression: &str,
+    variables: &HashMap<String, f64, S>,
+) -> Option<Vec<Token>> {
     let mut output_queue: Vec<Token> = Vec::new();
     let mut operator_stack: Vec<Token> = Vec::new();
     let operators: HashMap<char, (u8, Associativity)> = [
@@ -23,7 +38,7 @@ fn shunting_yard(expression: &str) -> Option<Vec<Token>> {
     .copied()
     .collect();
 
-    let tokens = tokenize(expression).ok()?;
+    let tokens = tokenize(expression, variables)?;
 
     for token in tokens {
         match token {
@@ -106,16 +121,28 @@ enum Associativity {
     Right,
 }
 
-fn tokenize(expression: &str) -> Result<Vec<Token>, <f64 as FromStr>::Err> {
+fn tokenize<S: BuildHasher>(
+    expression: &str,
+    variables: &HashMap<String, f64, S>,
+) -> Option<Vec<Token>> {
     let mut tokens: Vec<Token> = Vec::new();
     let mut current_number = String::new();
+    let mut current_variable = String::new();
 
     for c in expression.chars() {
         match c {
+            'a'..='z' | 'A'..='Z' | '_' => current_variable.push(c),
+            '0'..='9' if !current_variable.is_empty() => current_variable.push(c),
             '0'..='9' | '.' => current_number.push(c),
             _ => {
+                if !current_variable.is_empty() {
+                    tokens.push(Token::Number(
+                        *variables.get(&current_variable.to_lowercase())?,
+                    ));
+                    current_variable.clear();
+                }
                 if !current_number.is_empty() {
-                    tokens.push(Token::Number(current_number.parse()?));
+                    tokens.push(Token::Number(current_number.parse().ok()?));
                     current_number.clear();
                 }
                 match c {
@@ -138,9 +165,14 @@ fn tokenize(expression: &str) -> Result<Vec<Token>, <f64 as FromStr>::Err> {
         }
     }
 
+    if !current_variable.is_empty() {
+        tokens.push(Token::Number(
+            *variables.get(&current_variable.to_lowercase())?,
+        ));
+    }
     if !current_number.is_empty() {
-        tokens.push(Token::Number(current_number.parse()?));
+        tokens.push(Token::Number(current_number.parse().ok()?));
     }
 
-    Ok(tokens)
+    Some(tokens)
 }
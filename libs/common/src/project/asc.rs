@@ -6,6 +6,10 @@ pub struct Options {
     enabled: Option<bool>,
     #[serde(default)]
     exclude: Vec<String>,
+    #[serde(default)]
+    line_markers: bool,
+    #[serde(default)]
+    native: bool,
 }
 
 impl Options {
@@ -15,8 +19,17 @@ impl Options {
     }
 
     #[must_use]
-    #[cfg(not(target_os = "macos"))]
     pub const fn exclude(&self) -> &Vec<String> {
         &self.exclude
     }
+
+    #[must_use]
+    pub const fn line_markers(&self) -> bool {
+        self.line_markers
+    }
+
+    #[must_use]
+    pub const fn native(&self) -> bool {
+        self.native
+    }
 }
@@ -19,6 +19,12 @@ pub struct Features {
 
     #[serde(default)]
     release: ReleaseOptions,
+
+    #[serde(default)]
+    defines: DefinesOptions,
+
+    #[serde(default)]
+    builtins: BuiltinsOptions,
 }
 
 impl Features {
@@ -45,6 +51,18 @@ impl Features {
     pub const fn release(&self) -> &ReleaseOptions {
         &self.release
     }
+
+    #[must_use]
+    /// Preprocessor defines
+    pub const fn defines(&self) -> &DefinesOptions {
+        &self.defines
+    }
+
+    #[must_use]
+    /// Built-in macros enabled for the project
+    pub const fn builtins(&self) -> &BuiltinsOptions {
+        &self.builtins
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
@@ -191,3 +209,132 @@ impl ReleaseOptions {
         }
     }
 }
+
+#[derive(PartialEq, Eq, Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+/// The value of a define in the project config
+pub enum DefineValue {
+    /// `true` is defined as `1`, `false` as `0`
+    Bool(bool),
+    /// A number
+    Number(i64),
+    /// Used as written, `""` defines a flag with no value
+    Text(String),
+}
+
+impl std::fmt::Display for DefineValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", u8::from(*value)),
+            Self::Number(value) => write!(f, "{value}"),
+            Self::Text(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// Preprocessor defines for each build mode
+pub struct DefinesOptions {
+    #[serde(default)]
+    /// Defines used by `hemtt dev` and `hemtt launch`
+    dev: HashMap<String, DefineValue>,
+    #[serde(default)]
+    /// Defines used by `hemtt build`
+    build: HashMap<String, DefineValue>,
+    #[serde(default)]
+    /// Defines used by `hemtt release`
+    release: HashMap<String, DefineValue>,
+}
+
+impl DefinesOptions {
+    #[must_use]
+    /// The defines for a build mode, sorted by name
+    ///
+    /// Unknown modes have no defines
+    pub fn mode(&self, mode: &str) -> Vec<(String, String)> {
+        let defines = match mode {
+            "dev" => &self.dev,
+            "build" => &self.build,
+            "release" => &self.release,
+            _ => return Vec::new(),
+        };
+        let mut defines = defines
+            .iter()
+            .map(|(name, value)| (name.clone(), value.to_string()))
+            .collect::<Vec<_>>();
+        defines.sort();
+        defines
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+/// Built-in macros that Arma defines from the time of the build and the game version
+pub struct BuiltinsOptions {
+    #[serde(default)]
+    /// Define `__DATE_STR__`, `__TIME__`, `__TIMESTAMP_UTC__` and the other date macros
+    date: bool,
+    #[serde(default)]
+    /// Define `__GAME_VER__`, `__GAME_VER_MAJ__`, `__GAME_VER_MIN__` and `__GAME_BUILD__`, as `major.minor.build`
+    game_version: Option<String>,
+}
+
+impl BuiltinsOptions {
+    #[must_use]
+    /// Should the date macros be defined
+    pub const fn date(&self) -> bool {
+        self.date
+    }
+
+    #[must_use]
+    /// The game version for the game version macros
+    pub fn game_version(&self) -> Option<&str> {
+        self.game_version.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Features;
+
+    #[test]
+    fn defines_mode() {
+        let features: Features = toml::from_str(
+            r#"
+[defines.dev]
+DEBUG_MODE_FULL = ""
+DISABLE_COMPILE_CACHE = true
+
+[defines.release]
+VERSION = 3
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            features.defines().mode("dev"),
+            vec![
+                ("DEBUG_MODE_FULL".to_string(), String::new()),
+                ("DISABLE_COMPILE_CACHE".to_string(), "1".to_string()),
+            ]
+        );
+        assert!(features.defines().mode("build").is_empty());
+        assert_eq!(
+            features.defines().mode("release"),
+            vec![("VERSION".to_string(), "3".to_string())]
+        );
+    }
+
+    #[test]
+    fn builtins() {
+        let features: Features = toml::from_str(
+            r#"
+[builtins]
+date = true
+game_version = "2.14.150957"
+"#,
+        )
+        .unwrap();
+        assert!(features.builtins().date());
+        assert_eq!(features.builtins().game_version(), Some("2.14.150957"));
+        assert!(!Features::default().builtins().date());
+    }
+}
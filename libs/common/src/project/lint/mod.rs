@@ -1,11 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+mod preprocessor;
 mod sqf;
 
 #[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
 pub struct Options {
     #[serde(default)]
     sqf: sqf::Options,
+    #[serde(default)]
+    preprocessor: preprocessor::Options,
 }
 
 impl Options {
@@ -13,4 +16,9 @@ impl Options {
     pub const fn sqf(&self) -> &sqf::Options {
         &self.sqf
     }
+
+    #[must_use]
+    pub const fn preprocessor(&self) -> &preprocessor::Options {
+        &self.preprocessor
+    }
 }
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Options {
+    #[serde(default)]
+    unused_macros: bool,
+    #[serde(default)]
+    unused_includes: bool,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+impl Options {
+    #[must_use]
+    pub const fn unused_macros(&self) -> bool {
+        self.unused_macros
+    }
+
+    #[must_use]
+    pub const fn unused_includes(&self) -> bool {
+        self.unused_includes
+    }
+
+    #[must_use]
+    pub const fn exclude(&self) -> &Vec<String> {
+        &self.exclude
+    }
+}
@@ -4,14 +4,16 @@ use std::fmt::{Debug, Display};
 
 mod error;
 mod output;
+mod pragma;
 mod processed;
 mod symbol;
 mod token;
 mod whitespace;
 
 pub use error::Error;
-pub use output::Output;
-pub use processed::{Mapping, Processed};
+pub use output::{Expansion, Output};
+pub use pragma::{Pragma, Scope};
+pub use processed::{MacroUsage, Mapping, Processed};
 pub use symbol::Symbol;
 pub use token::Token;
 pub use whitespace::Whitespace;
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use super::{Symbol, Token};
 
@@ -6,9 +6,20 @@ use super::{Symbol, Token};
 /// The output of a token
 pub enum Output {
     /// The token did not expand
-    Direct(Rc<Token>),
+    Direct(Arc<Token>),
     /// The token expanded to a list of tokens
-    Macro(Rc<Token>, Vec<Self>),
+    Macro(Arc<Token>, Vec<Self>, Expansion),
+}
+
+#[derive(Debug)]
+/// Where an expanded macro was defined, and what it was called with
+pub struct Expansion {
+    /// The name of the macro where it was defined
+    pub definition: Arc<Token>,
+    /// The tokens of each argument, empty if the macro is not a function
+    pub arguments: Vec<Vec<Arc<Token>>>,
+    /// Macros used in the arguments, expanded before the call
+    pub nested: Vec<Output>,
 }
 
 impl Output {
@@ -16,7 +27,7 @@ impl Output {
     pub fn last_symbol(&self) -> Option<&Symbol> {
         match self {
             Self::Direct(t) => Some(t.symbol()),
-            Self::Macro(_, t) => t.last().and_then(Self::last_symbol),
+            Self::Macro(_, t, _) => t.last().and_then(Self::last_symbol),
         }
     }
 }
@@ -25,7 +36,7 @@ impl ToString for Output {
     fn to_string(&self) -> String {
         match self {
             Self::Direct(t) => t.to_string(),
-            Self::Macro(_, t) => t
+            Self::Macro(_, t, _) => t
                 .iter()
                 .map(std::string::ToString::to_string)
                 .collect::<String>(),
@@ -33,11 +44,20 @@ impl ToString for Output {
     }
 }
 
-impl From<Output> for Vec<Rc<Token>> {
+impl From<&Output> for Vec<Arc<Token>> {
+    fn from(value: &Output) -> Self {
+        match value {
+            Output::Direct(t) => vec![t.clone()],
+            Output::Macro(_, t, _) => t.iter().flat_map(<&Output as Into<Self>>::into).collect(),
+        }
+    }
+}
+
+impl From<Output> for Vec<Arc<Token>> {
     fn from(value: Output) -> Self {
         match value {
             Output::Direct(t) => vec![t],
-            Output::Macro(_, t) => t
+            Output::Macro(_, t, _) => t
                 .into_iter()
                 .flat_map(<Output as Into<Self>>::into)
                 .collect(),
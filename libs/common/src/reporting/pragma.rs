@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use crate::workspace::WorkspacePath;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Where a `#pragma hemtt` directive applies
+pub enum Scope {
+    /// The line following the pragma
+    Line,
+    /// The file containing the pragma
+    File,
+    /// The file containing the pragma, and every file it includes after the pragma
+    Include,
+}
+
+impl FromStr for Scope {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "line" => Ok(Self::Line),
+            "file" => Ok(Self::File),
+            "include" => Ok(Self::Include),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    name: String,
+    scope: Scope,
+    path: String,
+    line: usize,
+}
+
+impl Entry {
+    fn applies(&self, path: &str, line: usize) -> bool {
+        self.path == path && (self.scope != Scope::Line || self.line == line)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+/// Suppressions set by `#pragma hemtt` directives
+pub struct Pragma {
+    suppressed: Vec<Entry>,
+}
+
+impl Pragma {
+    /// Suppress a code, `line` is the line of the pragma
+    ///
+    /// The code can be the ident, `PW1`, or the name of the code, `pw1_redefine`
+    pub fn suppress(&mut self, code: &str, scope: Scope, path: &WorkspacePath, line: usize) {
+        self.suppressed.push(Entry {
+            name: code
+                .split_once('_')
+                .map_or(code, |(ident, _)| ident)
+                .to_uppercase(),
+            scope,
+            path: path.as_str().to_string(),
+            line: line + 1,
+        });
+    }
+
+    /// Carry the include scoped suppressions of a file into a file it includes
+    pub fn include(&mut self, from: &WorkspacePath, to: &WorkspacePath) {
+        let carried = self
+            .suppressed
+            .iter()
+            .filter(|e| e.scope == Scope::Include && e.path == from.as_str())
+            .map(|e| Entry {
+                path: to.as_str().to_string(),
+                ..e.clone()
+            })
+            .collect::<Vec<_>>();
+        for entry in carried {
+            if !self.suppressed.contains(&entry) {
+                self.suppressed.push(entry);
+            }
+        }
+    }
+
+    #[must_use]
+    /// Check if a code is suppressed at a line of a file
+    pub fn is_suppressed(&self, code: &str, path: &str, line: usize) -> bool {
+        self.suppressed
+            .iter()
+            .any(|e| e.name.eq_ignore_ascii_case(code) && e.applies(path, line))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::workspace::Workspace;
+
+    use super::{Pragma, Scope};
+
+    #[test]
+    fn suppress_scopes() {
+        let workspace = Workspace::builder().memory().finish(None).unwrap();
+        let source = workspace.join("source.hpp").unwrap();
+        let included = workspace.join("included.hpp").unwrap();
+        let mut pragma = Pragma::default();
+        pragma.suppress("pw1_redefine", Scope::Line, &source, 3);
+        pragma.suppress("CW1", Scope::Include, &source, 1);
+        pragma.include(&source, &included);
+        assert!(pragma.is_suppressed("PW1", "/source.hpp", 4));
+        assert!(!pragma.is_suppressed("PW1", "/source.hpp", 5));
+        assert!(pragma.is_suppressed("CW1", "/source.hpp", 10));
+        assert!(pragma.is_suppressed("CW1", "/included.hpp", 1));
+        assert!(!pragma.is_suppressed("CW2", "/included.hpp", 1));
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(feature = "lsp")]
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::{fmt::Write, sync::Arc};
 
 use crate::{
     position::{LineCol, Position},
@@ -8,7 +8,7 @@ use crate::{
     workspace::WorkspacePath,
 };
 
-use super::{Code, Error};
+use super::{Code, Error, Pragma};
 
 #[derive(Debug, Default)]
 /// A processed file
@@ -16,9 +16,15 @@ pub struct Processed {
     sources: Vec<(WorkspacePath, String)>,
     processed: String,
 
+    /// The output of the preprocessor, with the macros that were expanded
+    output: Vec<Output>,
+
     /// character offset for each line
     line_offsets: Vec<usize>,
 
+    /// original position of the first token on each line
+    line_origins: Vec<Option<Position>>,
+
     /// string offset(start, stop), source, source position
     mappings: Vec<Mapping>,
 
@@ -38,12 +44,30 @@ pub struct Processed {
 
     /// Warnings
     warnings: Vec<Box<dyn Code>>,
+
+    /// Suppressions from `#pragma hemtt`
+    pragma: Pragma,
+
+    /// Files included by `#include`, as (file, included file)
+    includes: Vec<(WorkspacePath, WorkspacePath)>,
+
+    /// Which macros and includes were used
+    macro_usage: MacroUsage,
+}
+
+#[derive(Debug, Default)]
+/// Which macros and includes were used while preprocessing a file
+pub struct MacroUsage {
+    /// The name of every macro defined by `#define`, and if it was used
+    pub macros: Vec<(Arc<Token>, bool)>,
+    /// The path of every `#include`, and if anything it defined or output was used
+    pub includes: Vec<(Arc<Token>, bool)>,
 }
 
 fn append_token(
     processed: &mut Processed,
     string_stack: &mut Vec<char>,
-    token: Rc<Token>,
+    token: &Arc<Token>,
 ) -> Result<(), Error> {
     let path = token.position().path().clone();
     let source = processed
@@ -90,7 +114,7 @@ fn append_token(
             }),
             source,
             original: token.position().clone(),
-            token,
+            token: token.clone(),
             was_macro: false,
         });
     } else {
@@ -113,7 +137,7 @@ fn append_token(
             }),
             source,
             original: token.position().clone(),
-            token,
+            token: token.clone(),
             was_macro: false,
         });
     }
@@ -123,14 +147,14 @@ fn append_token(
 fn append_output(
     processed: &mut Processed,
     string_stack: &mut Vec<char>,
-    output: Vec<Output>,
+    output: &[Output],
 ) -> Result<(), Error> {
     for o in output {
         match o {
             Output::Direct(t) => {
                 append_token(processed, string_stack, t)?;
             }
-            Output::Macro(root, o) => {
+            Output::Macro(root, o, _) => {
                 let start = processed.total;
                 let line = processed.line;
                 let col = processed.col;
@@ -156,7 +180,7 @@ fn append_output(
                     ),
                     source,
                     original: root.position().clone(),
-                    token: root,
+                    token: root.clone(),
                     was_macro: true,
                 });
             }
@@ -168,6 +192,8 @@ fn append_output(
 impl Processed {
     /// Process the output of the preprocessor
     ///
+    /// Warnings suppressed by the pragma are removed
+    ///
     /// # Errors
     /// [`Error::Workspace`] if a workspace path could not be read
     pub fn new(
@@ -175,17 +201,36 @@ impl Processed {
         #[cfg(feature = "lsp")] usage: HashMap<Position, Vec<Position>>,
         #[cfg(feature = "lsp")] declarations: HashMap<Position, Position>,
         warnings: Vec<Box<dyn Code>>,
+        pragma: Pragma,
+        includes: Vec<(WorkspacePath, WorkspacePath)>,
+        macro_usage: MacroUsage,
     ) -> Result<Self, Error> {
         let mut processed = Self {
             #[cfg(feature = "lsp")]
             declarations,
             #[cfg(feature = "lsp")]
             usage,
-            warnings,
+            pragma,
+            includes,
+            macro_usage,
             ..Default::default()
         };
         let mut string_stack = Vec::new();
-        append_output(&mut processed, &mut string_stack, output)?;
+        for o in &output {
+            let origin = match o {
+                Output::Direct(token) | Output::Macro(token, _, _) => token.position(),
+            };
+            if processed.line_origins.len() <= processed.line {
+                processed.line_origins.resize(processed.line + 1, None);
+            }
+            processed.line_origins[processed.line].get_or_insert_with(|| origin.clone());
+            append_output(&mut processed, &mut string_stack, std::slice::from_ref(o))?;
+        }
+        processed.output = output;
+        processed.warnings = warnings
+            .into_iter()
+            .filter(|w| !processed.is_suppressed(w.as_ref()))
+            .collect();
         Ok(processed)
     }
 
@@ -196,6 +241,34 @@ impl Processed {
         &self.processed
     }
 
+    #[must_use]
+    /// Get the output with `#line N "file"` markers, so other tools can map it back to the original files
+    ///
+    /// A marker is written before each line that does not follow on from the previous line of the same file
+    pub fn as_string_with_lines(&self) -> String {
+        let mut out = String::with_capacity(self.processed.len());
+        let mut next: Option<(&str, usize)> = None;
+        for (index, line) in self.processed.split_inclusive('\n').enumerate() {
+            if let Some(origin) = self.line_origins.get(index).and_then(Option::as_ref) {
+                let current = (origin.path().as_str(), origin.start().line());
+                if next != Some(current) {
+                    writeln!(out, "#line {} \"{}\"", current.1, current.0)
+                        .expect("writing to a string");
+                }
+                next = Some(current);
+            }
+            out.push_str(line);
+            next = next.map(|(path, line)| (path, line + 1));
+        }
+        out
+    }
+
+    #[must_use]
+    /// Get the output of the preprocessor, including the macros that were expanded
+    pub fn output(&self) -> &[Output] {
+        &self.output
+    }
+
     #[must_use]
     /// Character offset for a line
     pub fn line_offset(&self, line: usize) -> Option<usize> {
@@ -248,6 +321,48 @@ impl Processed {
     pub fn warnings(&self) -> &[Box<dyn Code>] {
         &self.warnings
     }
+
+    #[must_use]
+    /// Returns every `#include`, as (file, included file), in the order they were included
+    pub fn includes(&self) -> &[(WorkspacePath, WorkspacePath)] {
+        &self.includes
+    }
+
+    #[must_use]
+    /// Returns every file that was included, directly or by another included file
+    pub fn dependencies(&self) -> Vec<&WorkspacePath> {
+        let mut dependencies = Vec::new();
+        for (_, included) in &self.includes {
+            if !dependencies.contains(&included) {
+                dependencies.push(included);
+            }
+        }
+        dependencies
+    }
+
+    #[must_use]
+    /// Returns which macros and includes were used
+    pub const fn macro_usage(&self) -> &MacroUsage {
+        &self.macro_usage
+    }
+
+    #[must_use]
+    /// Returns the suppressions from `#pragma hemtt`
+    pub const fn pragma(&self) -> &Pragma {
+        &self.pragma
+    }
+
+    #[must_use]
+    /// Check if a code is suppressed by `#pragma hemtt suppress` where it is reported
+    pub fn is_suppressed(&self, code: &dyn Code) -> bool {
+        code.ci_generate()
+            .into_iter()
+            .chain(code.ci_generate_processed(self))
+            .any(|a| {
+                self.pragma
+                    .is_suppressed(code.ident(), &a.path, a.start_line)
+            })
+    }
 }
 
 #[derive(Debug)]
@@ -256,7 +371,7 @@ pub struct Mapping {
     source: usize,
     processed: (LineCol, LineCol),
     original: Position,
-    token: Rc<Token>,
+    token: Arc<Token>,
     was_macro: bool,
 }
 
@@ -293,7 +408,7 @@ impl Mapping {
 
     #[must_use]
     /// Get the original token
-    pub const fn token(&self) -> &Rc<Token> {
+    pub const fn token(&self) -> &Arc<Token> {
         &self.token
     }
 
@@ -22,6 +22,7 @@ use crate::{
 pub struct Workspace {
     pub(crate) vfs: VfsPath,
     pub(crate) project: Option<ProjectConfig>,
+    pub(crate) defines: Vec<(String, String)>,
     pub(crate) pointers: HashMap<String, VfsPath>,
     pub(crate) addons: Vec<VfsPath>,
     pub(crate) missions: Vec<VfsPath>,
@@ -40,14 +41,29 @@ impl Workspace {
         self.project.as_ref()
     }
 
+    #[must_use]
+    /// Returns the defines added to every preprocessed file
+    pub fn defines(&self) -> &[(String, String)] {
+        &self.defines
+    }
+
     /// Create a new workspace from a vfs path
     ///
     /// # Errors
     /// [`Error::Vfs`] if the workspace could not be created
     pub fn create(vfs: VfsPath, project: Option<ProjectConfig>) -> Result<WorkspacePath, Error> {
+        Self::create_with_defines(vfs, project, Vec::new())
+    }
+
+    fn create_with_defines(
+        vfs: VfsPath,
+        project: Option<ProjectConfig>,
+        defines: Vec<(String, String)>,
+    ) -> Result<WorkspacePath, Error> {
         let mut workspace = Self {
             vfs,
             project,
+            defines,
             pointers: HashMap::new(),
             addons: Vec::new(),
             missions: Vec::new(),
@@ -93,6 +109,7 @@ impl Workspace {
 /// A workspace builder
 pub struct WorkspaceBuilder {
     layers: Vec<VfsPath>,
+    defines: Vec<(String, String)>,
 }
 
 impl WorkspaceBuilder {
@@ -111,6 +128,15 @@ impl WorkspaceBuilder {
         self
     }
 
+    #[must_use]
+    /// Add defines to every preprocessed file, as `(name, value)`
+    ///
+    /// An empty value defines a flag, later defines replace earlier ones with the same name
+    pub fn defines(mut self, defines: Vec<(String, String)>) -> Self {
+        self.defines.extend(defines);
+        self
+    }
+
     /// Finish building the workspace
     ///
     /// # Errors
@@ -118,6 +144,6 @@ impl WorkspaceBuilder {
     pub fn finish(self, project: Option<ProjectConfig>) -> Result<WorkspacePath, Error> {
         let mut layers = self.layers;
         layers.reverse();
-        Workspace::create(OverlayFS::new(&layers).into(), project)
+        Workspace::create_with_defines(OverlayFS::new(&layers).into(), project, self.defines)
     }
 }
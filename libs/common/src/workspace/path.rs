@@ -104,6 +104,15 @@ impl WorkspacePath {
         self.path.as_str()
     }
 
+    #[must_use]
+    /// Get the root of the workspace
+    pub fn root(&self) -> Self {
+        Self {
+            path: self.path.root(),
+            workspace: self.workspace.clone(),
+        }
+    }
+
     #[must_use]
     /// Get the parent of the path
     pub fn parent(&self) -> Self {
@@ -25,3 +25,23 @@ fn negation() {
     assert_eq!(hemtt_common::math::eval("1 + -1 * 2 / 2"), Some(0.0));
     assert_eq!(hemtt_common::math::eval("1 - -1"), Some(2.0));
 }
+
+#[test]
+fn variables() {
+    let variables = std::collections::HashMap::from([
+        ("_spacing".to_string(), 0.5),
+        ("count".to_string(), 4.0),
+    ]);
+    assert_eq!(
+        hemtt_common::math::eval_with_variables("_spacing * 2", &variables),
+        Some(1.0)
+    );
+    assert_eq!(
+        hemtt_common::math::eval_with_variables("(Count + 1) * _Spacing", &variables),
+        Some(2.5)
+    );
+    assert_eq!(
+        hemtt_common::math::eval_with_variables("missing + 1", &variables),
+        None
+    );
+}
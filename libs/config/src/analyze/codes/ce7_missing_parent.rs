@@ -1,20 +1,19 @@
 use ariadne::{sources, ColorGenerator, Fmt, Label, Report};
 use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
 
-use crate::Class;
+use crate::{analyze::similar::did_you_mean, Class};
 
 pub struct MissingParent {
     class: Class,
+    similar: Vec<String>,
 }
 
 impl MissingParent {
-    pub const fn new(class: Class) -> Self {
-        Self { class }
+    pub const fn new(class: Class, similar: Vec<String>) -> Self {
+        Self { class, similar }
     }
 }
 
-// TODO: maybe we could have a `did you mean` here without too much trouble?
-
 impl Code for MissingParent {
     fn ident(&self) -> &'static str {
         "CE7"
@@ -29,6 +28,9 @@ impl Code for MissingParent {
     }
 
     fn help(&self) -> Option<String> {
+        if let Some(similar) = did_you_mean(&self.similar) {
+            return Some(similar);
+        }
         self.class.parent().map(|parent| {
             format!(
                 "add `class {};` to the config to declare it as external",
@@ -54,6 +56,21 @@ impl Code for MissingParent {
         let mut out = Vec::new();
         let mut colors = ColorGenerator::new();
         let a = colors.next();
+        let help = if self.similar.is_empty() {
+            format!(
+                "add `class {};` to the config to declare it as external",
+                parent.as_str().fg(a),
+            )
+        } else {
+            format!(
+                "did you mean `{}`",
+                self.similar
+                    .iter()
+                    .map(|dym| format!("{}", dym.fg(a)))
+                    .collect::<Vec<_>>()
+                    .join("`, `")
+            )
+        };
         Report::build(
             ariadne::ReportKind::Error,
             token.position().path().as_str(),
@@ -69,10 +86,7 @@ impl Code for MissingParent {
             .with_message(self.label_message())
             .with_color(a),
         )
-        .with_help(format!(
-            "add `class {};` to the config to declare it as external",
-            parent.as_str().fg(a),
-        ))
+        .with_help(help)
         .finish()
         .write_for_stdout(sources(processed.sources_adrianne()), &mut out)
         .unwrap();
@@ -84,11 +98,15 @@ impl Code for MissingParent {
             .mapping(self.class.parent().unwrap().span.start)
             .unwrap();
         let map_file = processed.source(map.source()).unwrap();
-        vec![self.annotation(
+        let mut annotation = self.annotation(
             AnnotationLevel::Error,
             map_file.0.as_str().to_string(),
             map.original(),
-        )]
+        );
+        if let Some(similar) = did_you_mean(&self.similar) {
+            annotation.message = format!("{}, {similar}", annotation.message);
+        }
+        vec![annotation]
     }
 
     #[cfg(feature = "lsp")]
@@ -0,0 +1,92 @@
+use ariadne::{sources, ColorGenerator, Fmt, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::{
+    analyze::similar::{did_you_mean, did_you_mean_colored},
+    Str,
+};
+
+/// Skeletons can come from a `model.cfg` in a parent folder, which binarize merges
+const INHERITED: &str =
+    "if the skeleton is defined in a model.cfg in a parent folder, declare it with `class name;`";
+
+pub struct UnknownSkeleton {
+    skeleton: Str,
+    similar: Vec<String>,
+}
+
+impl UnknownSkeleton {
+    pub const fn new(skeleton: Str, similar: Vec<String>) -> Self {
+        Self { skeleton, similar }
+    }
+}
+
+impl Code for UnknownSkeleton {
+    fn ident(&self) -> &'static str {
+        "CW10"
+    }
+
+    fn message(&self) -> String {
+        "skeleton was not found in CfgSkeletons".to_string()
+    }
+
+    fn label_message(&self) -> String {
+        format!("no matching skeleton was found: `{}`", self.skeleton.value)
+    }
+
+    fn help(&self) -> Option<String> {
+        did_you_mean(&self.similar).or_else(|| Some(INHERITED.to_string()))
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = processed.mapping(self.skeleton.span.start).unwrap();
+        let value_token_start = map.token();
+        let map = processed.mapping(self.skeleton.span.end).unwrap();
+        let value_token_end = map.token();
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let color = colors.next();
+        Report::build(
+            ariadne::ReportKind::Warning,
+            value_token_start.position().path().as_str(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                value_token_start.position().path().to_string(),
+                value_token_start.position().start().0..value_token_end.position().end().0,
+            ))
+            .with_message(format!(
+                "no matching skeleton was found: `{}`",
+                self.skeleton.value.as_str().fg(color)
+            ))
+            .with_color(color),
+        )
+        .with_help(
+            did_you_mean_colored(&self.similar, color).unwrap_or_else(|| INHERITED.to_string()),
+        )
+        .finish()
+        .write_for_stdout(sources(processed.sources_adrianne()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let map = processed.mapping(self.skeleton.span.start).unwrap();
+        let map_file = processed.source(map.source()).unwrap();
+        let mut annotation = self.annotation(
+            AnnotationLevel::Warning,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        );
+        if let Some(similar) = did_you_mean(&self.similar) {
+            annotation.message = format!("{}, {similar}", annotation.message);
+        }
+        vec![annotation]
+    }
+
+    #[cfg(feature = "lsp")]
+    fn generate_processed_lsp(&self, processed: &Processed) -> Vec<(vfs::VfsPath, Diagnostic)> {}
+}
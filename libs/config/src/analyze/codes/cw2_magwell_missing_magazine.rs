@@ -3,22 +3,34 @@ use std::ops::Range;
 use ariadne::{sources, ColorGenerator, Fmt, Label, Report};
 use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
 
-use crate::Ident;
+use crate::{
+    analyze::similar::{did_you_mean, did_you_mean_colored},
+    Ident,
+};
 
 pub struct MagwellMissingMagazine {
     array: Ident,
     ident: String,
     span: Range<usize>,
+    similar: Vec<String>,
 }
 
 impl MagwellMissingMagazine {
-    pub const fn new(array: Ident, ident: String, span: Range<usize>) -> Self {
-        Self { array, ident, span }
+    pub const fn new(
+        array: Ident,
+        ident: String,
+        span: Range<usize>,
+        similar: Vec<String>,
+    ) -> Self {
+        Self {
+            array,
+            ident,
+            span,
+            similar,
+        }
     }
 }
 
-// TODO: maybe we could have a `did you mean` here without too much trouble?
-
 impl Code for MagwellMissingMagazine {
     fn ident(&self) -> &'static str {
         "CW2"
@@ -33,7 +45,7 @@ impl Code for MagwellMissingMagazine {
     }
 
     fn help(&self) -> Option<String> {
-        None
+        did_you_mean(&self.similar)
     }
 
     fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
@@ -46,7 +58,7 @@ impl Code for MagwellMissingMagazine {
         let mut out = Vec::new();
         let mut colors = ColorGenerator::new();
         let color = colors.next();
-        Report::build(
+        let mut report = Report::build(
             ariadne::ReportKind::Warning,
             array_token.position().path().as_str(),
             map.original_column(),
@@ -63,21 +75,29 @@ impl Code for MagwellMissingMagazine {
                 self.ident.as_str().fg(color)
             ))
             .with_color(color),
-        )
-        .finish()
-        .write_for_stdout(sources(processed.sources_adrianne()), &mut out)
-        .unwrap();
+        );
+        if let Some(help) = did_you_mean_colored(&self.similar, color) {
+            report = report.with_help(help);
+        }
+        report
+            .finish()
+            .write_for_stdout(sources(processed.sources_adrianne()), &mut out)
+            .unwrap();
         Some(String::from_utf8(out).unwrap())
     }
 
     fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
         let map = processed.mapping(self.span.start).unwrap();
         let map_file = processed.source(map.source()).unwrap();
-        vec![self.annotation(
+        let mut annotation = self.annotation(
             AnnotationLevel::Warning,
             map_file.0.as_str().to_string(),
             map.original(),
-        )]
+        );
+        if let Some(similar) = did_you_mean(&self.similar) {
+            annotation.message = format!("{}, {similar}", annotation.message);
+        }
+        vec![annotation]
     }
 
     #[cfg(feature = "lsp")]
@@ -0,0 +1,79 @@
+use ariadne::{sources, ColorGenerator, Fmt, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::Ident;
+
+pub struct DeleteNotInherited {
+    name: Ident,
+}
+
+impl DeleteNotInherited {
+    pub const fn new(name: Ident) -> Self {
+        Self { name }
+    }
+}
+
+impl Code for DeleteNotInherited {
+    fn ident(&self) -> &'static str {
+        "CW5"
+    }
+
+    fn message(&self) -> String {
+        "deleted class is not inherited from a parent".to_string()
+    }
+
+    fn label_message(&self) -> String {
+        "not inherited from a parent".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "remove `delete {};`, there is no inherited class to delete",
+            self.name.as_str()
+        ))
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = processed.mapping(self.name.span.start).unwrap();
+        let token = map.token();
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let a = colors.next();
+        Report::build(
+            ariadne::ReportKind::Warning,
+            token.position().path().as_str(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                token.position().path().to_string(),
+                token.position().start().0..token.position().end().0,
+            ))
+            .with_message(self.label_message())
+            .with_color(a),
+        )
+        .with_help(format!(
+            "remove `delete {};`, there is no inherited class to delete",
+            self.name.as_str().fg(a),
+        ))
+        .finish()
+        .write_for_stdout(sources(processed.sources_adrianne()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let map = processed.mapping(self.name.span.start).unwrap();
+        let map_file = processed.source(map.source()).unwrap();
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        )]
+    }
+
+    #[cfg(feature = "lsp")]
+    fn generate_processed_lsp(&self, processed: &Processed) -> Vec<(vfs::VfsPath, Diagnostic)> {}
+}
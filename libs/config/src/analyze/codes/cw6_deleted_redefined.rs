@@ -0,0 +1,91 @@
+use ariadne::{sources, ColorGenerator, Fmt, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::Ident;
+
+pub struct DeletedRedefined {
+    delete: Ident,
+    class: Ident,
+}
+
+impl DeletedRedefined {
+    pub const fn new(delete: Ident, class: Ident) -> Self {
+        Self { delete, class }
+    }
+}
+
+impl Code for DeletedRedefined {
+    fn ident(&self) -> &'static str {
+        "CW6"
+    }
+
+    fn message(&self) -> String {
+        "class is deleted and then redefined in the same scope".to_string()
+    }
+
+    fn label_message(&self) -> String {
+        "redefined after being deleted".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(format!(
+            "remove either `delete {};` or the class definition",
+            self.class.as_str()
+        ))
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = processed.mapping(self.class.span.start).unwrap();
+        let token = map.token();
+        let delete_map = processed.mapping(self.delete.span.start).unwrap();
+        let delete_token = delete_map.token();
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let color_class = colors.next();
+        let color_delete = colors.next();
+        Report::build(
+            ariadne::ReportKind::Warning,
+            token.position().path().as_str(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                token.position().path().to_string(),
+                token.position().start().0..token.position().end().0,
+            ))
+            .with_message(self.label_message())
+            .with_color(color_class),
+        )
+        .with_label(
+            Label::new((
+                delete_token.position().path().to_string(),
+                delete_token.position().start().0..delete_token.position().end().0,
+            ))
+            .with_message("deleted here")
+            .with_color(color_delete),
+        )
+        .with_help(format!(
+            "remove either `{}` or the class definition",
+            format!("delete {};", self.class.as_str()).fg(color_delete),
+        ))
+        .finish()
+        .write_for_stdout(sources(processed.sources_adrianne()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let map = processed.mapping(self.class.span.start).unwrap();
+        let map_file = processed.source(map.source()).unwrap();
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        )]
+    }
+
+    #[cfg(feature = "lsp")]
+    fn generate_processed_lsp(&self, processed: &Processed) -> Vec<(vfs::VfsPath, Diagnostic)> {}
+}
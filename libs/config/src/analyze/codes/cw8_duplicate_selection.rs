@@ -0,0 +1,86 @@
+use ariadne::{sources, ColorGenerator, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::Str;
+
+pub struct DuplicateSelection {
+    kind: &'static str,
+    conflicts: Vec<Str>,
+}
+
+impl DuplicateSelection {
+    pub const fn new(kind: &'static str, conflicts: Vec<Str>) -> Self {
+        Self { kind, conflicts }
+    }
+}
+
+impl Code for DuplicateSelection {
+    fn ident(&self) -> &'static str {
+        "CW8"
+    }
+
+    fn message(&self) -> String {
+        format!("{} was defined more than once", self.kind)
+    }
+
+    fn label_message(&self) -> String {
+        format!("duplicate {}", self.kind)
+    }
+
+    fn help(&self) -> Option<String> {
+        None
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let first = self.conflicts.first().unwrap();
+        let first_map = processed.mapping(first.span.start).unwrap();
+        let first_file = processed.source(first_map.source()).unwrap();
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+
+        Report::build(
+            ariadne::ReportKind::Warning,
+            first_file.0.to_string(),
+            first_map.original().start().offset(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_labels(self.conflicts.iter().map(|b| {
+            let map_start = processed.mapping(b.span.start).unwrap();
+            let map_end = processed.mapping(b.span.end).unwrap();
+            let token_start = map_start.token();
+            let token_end = map_end.token();
+            Label::new((
+                token_start.position().path().to_string(),
+                token_start.position().start().0..token_end.position().end().0,
+            ))
+            .with_color(colors.next())
+            .with_message(if b == first {
+                "first defined here"
+            } else {
+                "also defined here"
+            })
+        }))
+        .finish()
+        .write_for_stdout(sources(processed.sources_adrianne()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let mut out = Vec::with_capacity(self.conflicts.len());
+        for conflict in &self.conflicts {
+            let map = processed.mapping(conflict.span.start).unwrap();
+            let map_file = processed.source(map.source()).unwrap();
+            out.push(self.annotation(
+                AnnotationLevel::Warning,
+                map_file.0.as_str().to_string(),
+                map.original(),
+            ));
+        }
+        out
+    }
+
+    #[cfg(feature = "lsp")]
+    fn generate_processed_lsp(&self, processed: &Processed) -> Vec<(vfs::VfsPath, Diagnostic)> {}
+}
@@ -6,5 +6,13 @@ pub mod ce5_unexpected_array;
 pub mod ce6_expected_array;
 pub mod ce7_missing_parent;
 
+pub mod cw10_unknown_skeleton;
 pub mod cw1_parent_case;
 pub mod cw2_magwell_missing_magazine;
+pub mod cw3_external_not_in_database;
+pub mod cw4_required_addon_not_found;
+pub mod cw5_delete_not_inherited;
+pub mod cw6_deleted_redefined;
+pub mod cw7_expand_not_inherited;
+pub mod cw8_duplicate_selection;
+pub mod cw9_animation_selection_missing;
@@ -1,15 +1,24 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
 use hemtt_common::project::ProjectConfig;
 use hemtt_common::reporting::{Code, Processed};
 
-use crate::{Class, Config, Ident, Item, Property, Str, Value};
+use crate::{
+    database::{join, ClassDatabase},
+    Class, Config, Ident, Item, Property, Str, Value,
+};
 
 use super::{
     codes::{
         ce3_duplicate_property::DuplicateProperty, ce7_missing_parent::MissingParent,
         cw1_parent_case::ParentCase, cw2_magwell_missing_magazine::MagwellMissingMagazine,
+        cw3_external_not_in_database::ExternalNotInDatabase,
+        cw4_required_addon_not_found::RequiredAddonNotFound,
+        cw5_delete_not_inherited::DeleteNotInherited, cw6_deleted_redefined::DeletedRedefined,
+        cw7_expand_not_inherited::ExpandNotInherited,
     },
+    model,
+    similar::similar,
     Analyze,
 };
 
@@ -30,6 +39,10 @@ impl Analyze for Config {
             .collect::<Vec<_>>();
         let mut defined = HashMap::new();
         warnings.extend(external_parent_case_warn(&self.0, &mut defined));
+        warnings.extend(inheritance_warn(&self.0));
+        if model::is_model(self) {
+            warnings.extend(model::warnings(self));
+        }
         if let Some(project) = project {
             warnings.extend(magwell_missing_magazine(project, self));
         }
@@ -37,49 +50,120 @@ impl Analyze for Config {
     }
 
     fn errors(&self, project: Option<&ProjectConfig>, processed: &Processed) -> Vec<Box<dyn Code>> {
+        self.errors_with_database(project, processed, None)
+    }
+}
+
+impl Config {
+    /// [`Analyze::errors`], with external classes and parents also looked up in the `database`
+    pub(crate) fn errors_with_database(
+        &self,
+        project: Option<&ProjectConfig>,
+        processed: &Processed,
+        database: Option<&ClassDatabase>,
+    ) -> Vec<Box<dyn Code>> {
         let mut errors = self
             .0
             .iter()
             .flat_map(|p| p.errors(project, processed))
             .collect::<Vec<_>>();
-        let mut defined = HashSet::new();
-        errors.extend(external_missing_error(&self.0, &mut defined));
         errors.extend(duplicate_properties(&self.0));
+        let mut defined = HashMap::new();
+        errors.extend(external_missing_error(
+            &self.0,
+            &mut defined,
+            database,
+            &[String::new()],
+        ));
         errors
     }
+
+    /// Warnings that depend on classes outside of the config
+    pub(crate) fn external_warnings(
+        &self,
+        project: Option<&ProjectConfig>,
+        database: Option<&ClassDatabase>,
+    ) -> Vec<Box<dyn Code>> {
+        let Some(database) = database else {
+            return Vec::new();
+        };
+        let prefix = project.map(|p| p.prefix().to_lowercase());
+        let mut warnings =
+            external_database_warn(&self.0, database, &[String::new()], prefix.as_deref());
+        warnings.extend(required_addons_missing(self, database, prefix.as_deref()));
+        warnings
+    }
 }
 
 fn external_missing_error(
     properties: &[Property],
-    defined: &mut HashSet<String>,
+    defined: &mut HashMap<String, String>,
+    database: Option<&ClassDatabase>,
+    scopes: &[String],
 ) -> Vec<Box<dyn Code>> {
     let mut errors: Vec<Box<dyn Code>> = Vec::new();
+    let mut siblings = HashMap::new();
     for property in properties {
         if let Property::Class(c) = property {
             match c {
                 Class::Root { properties } => {
-                    errors.extend(external_missing_error(properties, defined));
+                    errors.extend(external_missing_error(
+                        properties, defined, database, scopes,
+                    ));
                 }
                 Class::External { name } => {
-                    let name = name.value.to_lowercase();
-                    if !defined.contains(&name) {
-                        defined.insert(name);
+                    let name_lower = name.value.to_lowercase();
+                    if let Some(database) = database {
+                        let inner = class_scopes(database, scopes, &siblings, &name_lower, None);
+                        siblings.insert(name_lower.clone(), inner);
                     }
+                    defined
+                        .entry(name_lower)
+                        .or_insert_with(|| name.value.clone());
                 }
                 Class::Local {
                     name,
                     parent,
                     properties,
                 } => {
-                    let name = name.value.to_lowercase();
-                    if let Some(parent) = parent {
-                        let parent = parent.value.to_lowercase();
-                        if parent != name && !defined.contains(&parent) {
-                            errors.push(Box::new(MissingParent::new(c.clone())));
+                    let name_lower = name.value.to_lowercase();
+                    let parent_lower = parent.as_ref().map(|p| p.value.to_lowercase());
+                    if let (Some(parent), Some(parent_lower)) = (parent, &parent_lower) {
+                        if parent_lower != &name_lower
+                            && !defined.contains_key(parent_lower)
+                            && !database.is_some_and(|database| {
+                                scopes
+                                    .iter()
+                                    .any(|s| database.contains(&join(s, parent_lower)))
+                            })
+                        {
+                            let mut names =
+                                defined.values().map(String::as_str).collect::<Vec<_>>();
+                            if let Some(database) = database {
+                                for scope in scopes {
+                                    names.extend(database.children(scope));
+                                }
+                            }
+                            errors.push(Box::new(MissingParent::new(
+                                c.clone(),
+                                similar(&parent.value, names),
+                            )));
                         }
                     }
-                    defined.insert(name);
-                    errors.extend(external_missing_error(properties, defined));
+                    let inner = database.map_or_else(Vec::new, |database| {
+                        class_scopes(
+                            database,
+                            scopes,
+                            &siblings,
+                            &name_lower,
+                            parent_lower.as_deref(),
+                        )
+                    });
+                    defined.insert(name_lower.clone(), name.value.clone());
+                    errors.extend(external_missing_error(
+                        properties, defined, database, &inner,
+                    ));
+                    siblings.insert(name_lower, inner);
                 }
             }
         }
@@ -87,6 +171,81 @@ fn external_missing_error(
     errors
 }
 
+fn external_database_warn(
+    properties: &[Property],
+    database: &ClassDatabase,
+    scopes: &[String],
+    prefix: Option<&str>,
+) -> Vec<Box<dyn Code>> {
+    let mut warnings: Vec<Box<dyn Code>> = Vec::new();
+    let mut siblings = HashMap::new();
+    for property in properties {
+        if let Property::Class(c) = property {
+            match c {
+                Class::Root { properties } => {
+                    warnings.extend(external_database_warn(properties, database, scopes, prefix));
+                }
+                Class::External { name } => {
+                    let name_lower = name.value.to_lowercase();
+                    let inner = class_scopes(database, scopes, &siblings, &name_lower, None);
+                    if inner.is_empty()
+                        && !scopes.is_empty()
+                        && !siblings.contains_key(&name_lower)
+                        && !prefix.is_some_and(|prefix| name_lower.starts_with(prefix))
+                    {
+                        warnings.push(Box::new(ExternalNotInDatabase::new(name.clone())));
+                    }
+                    siblings.insert(name_lower, inner);
+                }
+                Class::Local {
+                    name,
+                    parent,
+                    properties,
+                } => {
+                    let name = name.value.to_lowercase();
+                    let parent = parent.as_ref().map(|p| p.value.to_lowercase());
+                    let inner = class_scopes(database, scopes, &siblings, &name, parent.as_deref());
+                    warnings.extend(external_database_warn(properties, database, &inner, prefix));
+                    siblings.insert(name, inner);
+                }
+            }
+        }
+    }
+    warnings
+}
+
+/// Find the paths in the database that the contents of a class can be found in
+///
+/// `scopes` are the database paths of the enclosing class, `siblings` are the
+/// classes already seen in the same scope
+fn class_scopes(
+    database: &ClassDatabase,
+    scopes: &[String],
+    siblings: &HashMap<String, Vec<String>>,
+    name: &str,
+    parent: Option<&str>,
+) -> Vec<String> {
+    let lookup = |name: &str| {
+        siblings.get(name).cloned().unwrap_or_else(|| {
+            scopes
+                .iter()
+                .flat_map(|s| database.ancestry(&join(s, name)))
+                .collect()
+        })
+    };
+    let mut found = lookup(name);
+    if let Some(parent) = parent {
+        if parent != name {
+            for path in lookup(parent) {
+                if !found.contains(&path) {
+                    found.push(path);
+                }
+            }
+        }
+    }
+    found
+}
+
 fn external_parent_case_warn(
     properties: &[Property],
     defined: &mut HashMap<String, Class>,
@@ -132,6 +291,110 @@ fn external_parent_case_warn(
     warnings
 }
 
+fn inheritance_warn(properties: &[Property]) -> Vec<Box<dyn Code>> {
+    let mut warnings: Vec<Box<dyn Code>> = Vec::new();
+    let mut deleted: HashMap<String, &Ident> = HashMap::new();
+    for (index, property) in properties.iter().enumerate() {
+        match property {
+            Property::Delete(name) => {
+                deleted.insert(name.value.to_lowercase(), name);
+            }
+            Property::Class(Class::Root { properties }) => {
+                warnings.extend(inheritance_warn(properties));
+            }
+            Property::Class(Class::Local {
+                name,
+                parent,
+                properties: inner,
+            }) => {
+                if let Some(delete) = deleted.get(&name.value.to_lowercase()) {
+                    warnings.push(Box::new(DeletedRedefined::new(
+                        (*delete).clone(),
+                        name.clone(),
+                    )));
+                }
+                if let Some(ancestry) = ancestry(&properties[..index], parent.as_ref()) {
+                    let inherited = ancestry.into_iter().flatten().collect::<Vec<_>>();
+                    for property in inner {
+                        match property {
+                            Property::Delete(delete)
+                                if parent.is_some()
+                                    && !inherited.iter().any(|p| {
+                                        p.is_class()
+                                            && p.name().value.eq_ignore_ascii_case(&delete.value)
+                                    }) =>
+                            {
+                                warnings.push(Box::new(DeleteNotInherited::new(delete.clone())));
+                            }
+                            Property::Entry {
+                                name,
+                                value: Value::Array(array),
+                                ..
+                            } if parent.is_some()
+                                && array.expand
+                                && !inherited.iter().any(|p| {
+                                    matches!(p, Property::Entry {
+                                        name: inherited,
+                                        value: Value::Array(_),
+                                        ..
+                                    } if inherited.value.eq_ignore_ascii_case(&name.value))
+                                }) =>
+                            {
+                                warnings.push(Box::new(ExpandNotInherited::new(name.clone())));
+                            }
+                            _ => (),
+                        }
+                    }
+                }
+                warnings.extend(inheritance_warn(inner));
+            }
+            _ => (),
+        }
+    }
+    warnings
+}
+
+/// The properties of every class a class inherits from, nearest first
+///
+/// `siblings` are the properties of the scope before the class, as a class
+/// can only inherit from a class that is defined before it.
+/// `None` if any class in the chain is not defined in the same scope, as
+/// its properties are unknown
+fn ancestry<'a>(
+    mut siblings: &'a [Property],
+    parent: Option<&Ident>,
+) -> Option<Vec<&'a [Property]>> {
+    let mut chain = Vec::new();
+    let mut current = parent.map(|p| p.value.to_lowercase());
+    while let Some(name) = current {
+        let Some((
+            index,
+            Class::Local {
+                parent, properties, ..
+            },
+        )) = siblings
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, p)| match p {
+                Property::Class(c @ Class::Local { name: n, .. })
+                    if n.value.to_lowercase() == name =>
+                {
+                    Some((i, c))
+                }
+                _ => None,
+            })
+        else {
+            return None;
+        };
+        // the parent's own parent must be defined before the parent
+        siblings = &siblings[..index];
+        chain.push(properties.as_slice());
+        current = parent.as_ref().map(|p| p.value.to_lowercase());
+    }
+    Some(chain)
+}
+
 fn duplicate_properties(properties: &[Property]) -> Vec<Box<dyn Code>> {
     let mut seen: HashMap<String, Vec<(bool, Ident)>> = HashMap::new();
     duplicate_properties_inner("", properties, &mut seen);
@@ -236,6 +499,7 @@ fn magwell_missing_magazine(project: &ProjectConfig, config: &Config) -> Vec<Box
                         name.clone(),
                         value.clone(),
                         span.clone(),
+                        similar(value, classes.iter().map(|c| c.value.as_str())),
                     )));
                 }
             }
@@ -243,3 +507,62 @@ fn magwell_missing_magazine(project: &ProjectConfig, config: &Config) -> Vec<Box
     }
     warnings
 }
+
+fn required_addons_missing(
+    config: &Config,
+    database: &ClassDatabase,
+    prefix: Option<&str>,
+) -> Vec<Box<dyn Code>> {
+    let mut warnings: Vec<Box<dyn Code>> = Vec::new();
+    let Some(Property::Class(Class::Local {
+        properties: patches,
+        ..
+    })) = config
+        .0
+        .iter()
+        .find(|p| p.name().value.to_lowercase() == "cfgpatches")
+    else {
+        return warnings;
+    };
+    let mut addons = database.children("cfgpatches").collect::<Vec<_>>();
+    for patch in patches {
+        if let Property::Class(Class::Local { name, .. }) = patch {
+            addons.push(name.value.as_str());
+        }
+    }
+    for patch in patches {
+        let Property::Class(Class::Local { properties, .. }) = patch else {
+            continue;
+        };
+        for property in properties {
+            let Property::Entry {
+                name,
+                value: Value::Array(required),
+                ..
+            } = property
+            else {
+                continue;
+            };
+            if name.value.to_lowercase() != "requiredaddons" {
+                continue;
+            }
+            for addon in &required.items {
+                let Item::Str(Str { value, span }) = addon else {
+                    continue;
+                };
+                let lower = value.to_lowercase();
+                if prefix.is_some_and(|prefix| lower.starts_with(prefix))
+                    || addons.iter().any(|a| a.to_lowercase() == lower)
+                {
+                    continue;
+                }
+                warnings.push(Box::new(RequiredAddonNotFound::new(
+                    value.clone(),
+                    span.clone(),
+                    similar(value, addons.iter().copied()),
+                )));
+            }
+        }
+    }
+    warnings
+}
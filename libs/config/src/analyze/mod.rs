@@ -5,8 +5,10 @@ mod array;
 mod class;
 pub mod codes;
 mod config;
+mod model;
 mod number;
 mod property;
+mod similar;
 mod str;
 mod value;
 
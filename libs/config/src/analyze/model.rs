@@ -0,0 +1,240 @@
+//! Checks for `model.cfg`, run when a config contains `CfgSkeletons` or `CfgModels`
+//!
+//! Classes declared with `class name;` are known, but their contents are not, as they
+//! are usually defined in a `model.cfg` in a parent folder that binarize merges.
+//!
+//! The `axis` of an animation is not checked, it names a point in the memory LOD of the
+//! model, not a bone, and the model itself is not read.
+
+use std::collections::HashSet;
+
+use hemtt_common::reporting::Code;
+
+use crate::{Class, Config, Item, Property, Str, Value};
+
+use super::{
+    codes::{
+        cw10_unknown_skeleton::UnknownSkeleton, cw8_duplicate_selection::DuplicateSelection,
+        cw9_animation_selection_missing::AnimationSelectionMissing,
+    },
+    similar::similar,
+};
+
+/// A class inside `CfgSkeletons` or `CfgModels`
+struct ModelClass<'a> {
+    name: &'a str,
+    parent: Option<String>,
+    properties: &'a [Property],
+    /// Declared with `class name;`, the properties are unknown
+    external: bool,
+}
+
+type Classes<'a> = Vec<ModelClass<'a>>;
+
+/// Find a class by name, ignoring case
+fn find<'a, 'b>(classes: &'b Classes<'a>, name: &str) -> Option<&'b ModelClass<'a>> {
+    classes.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+/// Whether the config contains classes that are only used by `model.cfg`
+pub fn is_model(config: &Config) -> bool {
+    config.0.iter().any(|p| {
+        p.is_class()
+            && ["cfgskeletons", "cfgmodels"].contains(&p.name().value.to_lowercase().as_str())
+    })
+}
+
+pub fn warnings(config: &Config) -> Vec<Box<dyn Code>> {
+    let mut warnings: Vec<Box<dyn Code>> = Vec::new();
+    let skeletons = root_classes(config, "cfgskeletons");
+    let mut check = |value: &Str| {
+        if !value.value.is_empty() && find(&skeletons, &value.value).is_none() {
+            warnings.push(Box::new(UnknownSkeleton::new(
+                value.clone(),
+                similar(&value.value, skeletons.iter().map(|s| s.name)),
+            )));
+        }
+    };
+    for skeleton in &skeletons {
+        if let Some(inherit) = string(skeleton.properties, "skeletoninherit") {
+            check(inherit);
+        }
+    }
+    for model in &root_classes(config, "cfgmodels") {
+        if let Some(skeleton) = string(model.properties, "skeletonname") {
+            check(skeleton);
+        }
+    }
+    for skeleton in &skeletons {
+        if let Some(bones) = array(skeleton.properties, "skeletonbones") {
+            // bones are pairs of the bone and its parent
+            warnings.extend(duplicates("bone", bones.into_iter().step_by(2)));
+        }
+    }
+    let models = root_classes(config, "cfgmodels");
+    for model in &models {
+        if let Some(sections) = array(model.properties, "sections") {
+            warnings.extend(duplicates("section", sections.into_iter()));
+        }
+        let Some(skeleton) = inherited(&models, model, |p| string(p, "skeletonname")) else {
+            continue;
+        };
+        let Some(bones) = bones(&skeletons, &skeleton.value) else {
+            continue;
+        };
+        let Some(animations) = inherited(&models, model, |p| {
+            p.iter().find_map(|p| match p {
+                Property::Class(Class::Local {
+                    name, properties, ..
+                }) if name.value.eq_ignore_ascii_case("animations") => Some(properties),
+                _ => None,
+            })
+        }) else {
+            continue;
+        };
+        for animation in animations {
+            let Property::Class(Class::Local { properties, .. }) = animation else {
+                continue;
+            };
+            let Some(selection) = string(properties, "selection") else {
+                continue;
+            };
+            if !bones.contains(&selection.value.to_lowercase()) {
+                warnings.push(Box::new(AnimationSelectionMissing::new(
+                    selection.clone(),
+                    skeleton.value.clone(),
+                    similar(&selection.value, bones.iter().map(String::as_str)),
+                )));
+            }
+        }
+    }
+    warnings
+}
+
+/// The classes inside a root class
+fn root_classes<'a>(config: &'a Config, root: &str) -> Classes<'a> {
+    let mut classes = Vec::new();
+    for property in &config.0 {
+        let Property::Class(Class::Local {
+            name, properties, ..
+        }) = property
+        else {
+            continue;
+        };
+        if name.value.to_lowercase() != root {
+            continue;
+        }
+        for property in properties {
+            match property {
+                Property::Class(Class::Local {
+                    name,
+                    parent,
+                    properties,
+                }) => classes.push(ModelClass {
+                    name: &name.value,
+                    parent: parent.as_ref().map(|p| p.value.to_lowercase()),
+                    properties,
+                    external: false,
+                }),
+                Property::Class(Class::External { name }) => classes.push(ModelClass {
+                    name: &name.value,
+                    parent: None,
+                    properties: &[],
+                    external: true,
+                }),
+                _ => {}
+            }
+        }
+    }
+    classes
+}
+
+/// Find a value on a class or the classes it inherits from
+fn inherited<'a, T>(
+    classes: &Classes<'a>,
+    class: &ModelClass<'a>,
+    lookup: impl Fn(&'a [Property]) -> Option<T>,
+) -> Option<T> {
+    let mut seen = HashSet::new();
+    let mut current = Some(class);
+    while let Some(class) = current {
+        if let Some(value) = lookup(class.properties) {
+            return Some(value);
+        }
+        let parent = class.parent.as_ref()?;
+        if !seen.insert(parent) {
+            return None;
+        }
+        current = find(classes, parent);
+    }
+    None
+}
+
+/// All bones of a skeleton, including those from `skeletonInherit`
+///
+/// `None` if the skeleton or one it inherits from is unknown, or external
+fn bones(skeletons: &Classes, name: &str) -> Option<HashSet<String>> {
+    let mut bones = HashSet::new();
+    let mut seen = HashSet::new();
+    let mut current = Some(name.to_lowercase());
+    while let Some(name) = current {
+        if !seen.insert(name.clone()) {
+            break;
+        }
+        let skeleton = find(skeletons, &name).filter(|s| !s.external)?;
+        if let Some(items) = array(skeleton.properties, "skeletonbones") {
+            bones.extend(items.iter().step_by(2).map(|b| b.value.to_lowercase()));
+        }
+        current = string(skeleton.properties, "skeletoninherit")
+            .filter(|s| !s.value.is_empty())
+            .map(|s| s.value.to_lowercase());
+    }
+    Some(bones)
+}
+
+fn duplicates<'a>(kind: &'static str, names: impl Iterator<Item = &'a Str>) -> Vec<Box<dyn Code>> {
+    let mut seen: Vec<(String, Vec<Str>)> = Vec::new();
+    for name in names {
+        let lower = name.value.to_lowercase();
+        if let Some((_, conflicts)) = seen.iter_mut().find(|(n, _)| *n == lower) {
+            conflicts.push(name.clone());
+        } else {
+            seen.push((lower, vec![name.clone()]));
+        }
+    }
+    seen.into_iter()
+        .filter(|(_, conflicts)| conflicts.len() > 1)
+        .map(|(_, conflicts)| Box::new(DuplicateSelection::new(kind, conflicts)) as Box<dyn Code>)
+        .collect()
+}
+
+fn string<'a>(properties: &'a [Property], name: &str) -> Option<&'a Str> {
+    properties.iter().find_map(|p| match p {
+        Property::Entry {
+            name: n,
+            value: Value::Str(value),
+            ..
+        } if n.value.to_lowercase() == name => Some(value),
+        _ => None,
+    })
+}
+
+fn array<'a>(properties: &'a [Property], name: &str) -> Option<Vec<&'a Str>> {
+    properties.iter().find_map(|p| match p {
+        Property::Entry {
+            name: n,
+            value: Value::Array(array),
+            ..
+        } if n.value.to_lowercase() == name => Some(
+            array
+                .items
+                .iter()
+                .filter_map(|i| match i {
+                    Item::Str(s) => Some(s),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
+    })
+}
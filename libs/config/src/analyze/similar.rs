@@ -0,0 +1,51 @@
+use ariadne::{Color, Fmt};
+use strsim::levenshtein;
+
+/// Find up to 3 names similar to `search`, ignoring case
+///
+/// Names that are identical to `search` are not suggested
+pub fn similar<'a>(search: &str, names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let search_lower = search.to_lowercase();
+    let mut similar = names
+        .into_iter()
+        .filter(|name| *name != search)
+        .map(|name| (name, levenshtein(&name.to_lowercase(), &search_lower)))
+        .filter(|(_, distance)| *distance <= 3)
+        .collect::<Vec<_>>();
+    similar.sort_by(|(a_name, a), (b_name, b)| a.cmp(b).then_with(|| a_name.cmp(b_name)));
+    let mut suggestions: Vec<String> = Vec::new();
+    for (name, _) in similar {
+        if suggestions.len() == 3 {
+            break;
+        }
+        if !suggestions.iter().any(|s| s.eq_ignore_ascii_case(name)) {
+            suggestions.push(name.to_string());
+        }
+    }
+    suggestions
+}
+
+/// Format suggestions as `did you mean` text, if there are any
+pub fn did_you_mean(similar: &[String]) -> Option<String> {
+    if similar.is_empty() {
+        None
+    } else {
+        Some(format!("did you mean `{}`", similar.join("`, `")))
+    }
+}
+
+/// Format suggestions as `did you mean` text for a report, with each name in `color`
+pub fn did_you_mean_colored(similar: &[String], color: Color) -> Option<String> {
+    if similar.is_empty() {
+        None
+    } else {
+        Some(format!(
+            "did you mean `{}`",
+            similar
+                .iter()
+                .map(|name| format!("{}", name.fg(color)))
+                .collect::<Vec<_>>()
+                .join("`, `")
+        ))
+    }
+}
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::Error;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "HashMap<String, Option<String>>")]
+/// A database of classes that exist outside of the project, such as vanilla Arma 3 or CBA
+///
+/// The database is a JSON object mapping the path of each class to the name of its parent.
+/// Paths are separated by `/`, starting from the root of the config.
+///
+/// ```json
+/// {
+///     "CfgVehicles": null,
+///     "CfgVehicles/Car": "LandVehicle",
+///     "CfgVehicles/Car_F": "Car"
+/// }
+/// ```
+pub struct ClassDatabase {
+    /// Lowercase path to the path as written and the parent
+    classes: HashMap<String, (String, Option<String>)>,
+}
+
+impl ClassDatabase {
+    /// Load a class database from a JSON string
+    ///
+    /// # Errors
+    /// [`Error::ClassDatabase`] if the JSON is invalid
+    pub fn from_json(source: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(source)?)
+    }
+
+    #[must_use]
+    /// The number of classes in the database
+    pub fn len(&self) -> usize {
+        self.classes.len()
+    }
+
+    #[must_use]
+    /// Check if the database is empty
+    pub fn is_empty(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    #[must_use]
+    /// Check if a class exists in the database, the path is case insensitive
+    pub fn contains(&self, path: &str) -> bool {
+        self.classes.contains_key(&path.to_lowercase())
+    }
+
+    #[must_use]
+    /// Get the parent of a class, the path is case insensitive
+    pub fn parent(&self, path: &str) -> Option<&str> {
+        self.classes
+            .get(&path.to_lowercase())
+            .and_then(|(_, parent)| parent.as_deref())
+    }
+
+    /// Get the names of the classes directly inside a class, the path is case insensitive
+    pub fn children<'a>(&'a self, path: &str) -> impl Iterator<Item = &'a str> {
+        let path = path.to_lowercase();
+        self.classes.iter().filter_map(move |(lower, (name, _))| {
+            let (scope, _) = lower.rsplit_once('/').unwrap_or(("", lower));
+            if scope == path {
+                Some(
+                    name.rsplit_once('/')
+                        .map_or(name.as_str(), |(_, name)| name),
+                )
+            } else {
+                None
+            }
+        })
+    }
+
+    #[must_use]
+    /// Get the path of a class followed by the paths of every class it inherits from
+    ///
+    /// Empty if the class is not in the database
+    pub fn ancestry(&self, path: &str) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut current = path.to_lowercase();
+        while let Some((_, parent)) = self.classes.get(&current) {
+            if chain.contains(&current) {
+                break;
+            }
+            chain.push(current.clone());
+            let Some(parent) = parent else {
+                break;
+            };
+            current = join(
+                current.rsplit_once('/').map_or("", |(scope, _)| scope),
+                &parent.to_lowercase(),
+            );
+        }
+        chain
+    }
+}
+
+/// Join a class name onto a database path
+pub fn join(scope: &str, name: &str) -> String {
+    if scope.is_empty() {
+        name.to_lowercase()
+    } else {
+        format!("{scope}/{}", name.to_lowercase())
+    }
+}
+
+impl From<HashMap<String, Option<String>>> for ClassDatabase {
+    fn from(classes: HashMap<String, Option<String>>) -> Self {
+        Self {
+            classes: classes
+                .into_iter()
+                .map(|(path, parent)| (path.to_lowercase(), (path, parent)))
+                .collect(),
+        }
+    }
+}
@@ -6,4 +6,7 @@ pub enum Error {
     #[error("The config file is invalid: {0}")]
     /// An ArmA config file is invalid
     ConfigInvalid(String),
+    #[error("The class database is invalid: {0}")]
+    /// The class database could not be parsed
+    ClassDatabase(#[from] serde_json::Error),
 }
@@ -7,6 +7,7 @@
 //! Requires that files first be tokenized by the [`hemtt_preprocessor`] crate.
 
 mod analyze;
+mod database;
 mod error;
 mod model;
 
@@ -15,6 +16,7 @@ use ariadne::{sources, Label, Report};
 use chumsky::{prelude::Simple, Parser};
 use hemtt_common::reporting::{Code, Processed};
 
+pub use database::ClassDatabase;
 pub use error::Error;
 use hemtt_common::project::ProjectConfig;
 pub use model::*;
@@ -23,20 +25,40 @@ pub mod rapify;
 
 /// Parse a config file
 ///
+/// Warnings suppressed by `#pragma hemtt suppress` are not returned
+///
 /// # Errors
 /// If the file is invalid
 pub fn parse(
     project: Option<&ProjectConfig>,
     processed: &Processed,
+) -> Result<ConfigReport, Vec<ChumskyCode>> {
+    parse_with_database(project, processed, None)
+}
+
+/// Parse a config file, checking external classes and parents against a [`ClassDatabase`]
+///
+/// Only `config.cpp` files should be checked, other configs do not extend the game's config
+///
+/// # Errors
+/// If the file is invalid
+pub fn parse_with_database(
+    project: Option<&ProjectConfig>,
+    processed: &Processed,
+    database: Option<&ClassDatabase>,
 ) -> Result<ConfigReport, Vec<ChumskyCode>> {
     let (config, errors) = parse::config().parse_recovery(processed.as_string());
     config.map_or_else(
         || Err(errors.iter().map(std::convert::Into::into).collect()),
         |config| {
+            let mut warnings = config.warnings(project, processed);
+            warnings.extend(config.external_warnings(project, database));
+            warnings.retain(|w| !processed.is_suppressed(w.as_ref()));
+            let errors = config.errors_with_database(project, processed, database);
             Ok(ConfigReport {
                 valid: config.valid(project),
-                warnings: config.warnings(project, processed),
-                errors: config.errors(project, processed),
+                warnings,
+                errors,
                 config,
             })
         },
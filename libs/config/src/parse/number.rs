@@ -3,7 +3,7 @@ use chumsky::prelude::*;
 use crate::Number;
 
 pub fn number() -> impl Parser<char, Number, Error = Simple<char>> {
-    just('-')
+    one_of("-+")
         .or_not()
         .then(choice((
             number_float_exponent().map_with_span(|value, span| Number::Float32 { value, span }),
@@ -30,14 +30,15 @@ pub fn number() -> impl Parser<char, Number, Error = Simple<char>> {
             }),
         )))
         .map(|(sign, value)| match sign {
-            Some(_) => value.negate(),
-            None => value,
+            Some('-') => value.negate(),
+            _ => value,
         })
 }
 
 fn number_hex() -> impl Parser<char, i64, Error = Simple<char>> {
     let digits = one_of("0123456789abcdefABCDEF").repeated().at_least(1);
     just("0x")
+        .or(just("0X"))
         .ignore_then(digits)
         .collect::<String>()
         .map(|value| i64::from_str_radix(&value, 16))
@@ -53,10 +54,8 @@ fn number_int() -> impl Parser<char, i64, Error = Simple<char>> {
 }
 
 fn number_float_exponent() -> impl Parser<char, f32, Error = Simple<char>> {
-    number_digits()
-        .chain(just('.'))
-        .or_not()
-        .chain::<char, _, _>(number_digits())
+    number_decimal()
+        .or(number_digits())
         .chain::<char, _, _>(one_of("eE"))
         .chain::<char, _, _>(one_of("-+").or_not())
         .chain::<char, _, _>(number_digits())
@@ -66,15 +65,20 @@ fn number_float_exponent() -> impl Parser<char, f32, Error = Simple<char>> {
 }
 
 fn number_float_basic() -> impl Parser<char, f32, Error = Simple<char>> {
-    number_digits()
-        .or_not()
-        .chain::<char, _, _>(just('.'))
-        .chain::<char, _, _>(number_digits())
+    number_decimal()
         .collect::<String>()
         .from_str::<f32>()
         .try_map(error_map)
 }
 
+/// Digits with a decimal point, either side may be omitted but not both
+fn number_decimal() -> impl Parser<char, Vec<char>, Error = Simple<char>> {
+    number_digits()
+        .chain(just('.'))
+        .chain::<char, _, _>(number_digits().or_not().map(Option::unwrap_or_default))
+        .or(just('.').chain(number_digits()))
+}
+
 fn number_digits() -> impl Parser<char, Vec<char>, Error = Simple<char>> {
     one_of("0123456789").repeated().at_least(1)
 }
@@ -117,6 +121,7 @@ mod tests {
         assert_eq!(super::number_hex().parse("0x123"), Ok(0x123));
         assert_eq!(super::number_hex().parse("0x123abc"), Ok(0x0012_3ABC));
         assert_eq!(super::number_hex().parse("0x123.456"), Ok(0x123));
+        assert_eq!(super::number_hex().parse("0X1F"), Ok(0x1F));
         assert!(super::number_hex().parse("abc").is_err());
     }
 
@@ -126,6 +131,9 @@ mod tests {
         assert_eq!(super::number_float_basic().parse("123.0abc"), Ok(123.0));
         assert_eq!(super::number_float_basic().parse("123.456"), Ok(123.456));
         assert_eq!(super::number_float_basic().parse("123.456abc"), Ok(123.456));
+        assert_eq!(super::number_float_basic().parse(".5"), Ok(0.5));
+        assert_eq!(super::number_float_basic().parse("5."), Ok(5.0));
+        assert!(super::number_float_basic().parse(".").is_err());
         assert!(super::number_float_basic().parse("abc").is_err());
     }
 
@@ -155,6 +163,34 @@ mod tests {
             super::number_float_exponent().parse("123.456E2"),
             Ok(12345.6)
         );
+        assert_eq!(super::number_float_exponent().parse("1e-05"), Ok(0.00001));
+        assert_eq!(super::number_float_exponent().parse(".5e-3"), Ok(0.0005));
+        assert_eq!(super::number_float_exponent().parse("5.e3"), Ok(5000.0));
         assert!(super::number_float_exponent().parse("abc").is_err());
     }
+
+    #[test]
+    fn number() {
+        assert_eq!(
+            super::number().parse("+5"),
+            Ok(crate::Number::Int32 {
+                value: 5,
+                span: 1..2
+            })
+        );
+        assert_eq!(
+            super::number().parse("-0x1F"),
+            Ok(crate::Number::Int32 {
+                value: -31,
+                span: 1..5
+            })
+        );
+        assert_eq!(
+            super::number().parse("-2.5E-3"),
+            Ok(crate::Number::Float32 {
+                value: -0.0025,
+                span: 1..7
+            })
+        );
+    }
 }
@@ -24,7 +24,10 @@ fn check(dir: &str) {
         .unwrap();
     let source = workspace.join("source.hpp").unwrap();
     let processed = Processor::run(&source).unwrap();
-    let parsed = hemtt_config::parse(None, &processed);
+    let database = std::fs::read_to_string(folder.join("classes.json"))
+        .ok()
+        .map(|json| hemtt_config::ClassDatabase::from_json(&json).unwrap());
+    let parsed = hemtt_config::parse_with_database(None, &processed, database.as_ref());
     match parsed {
         Ok(config) => {
             let mut expected = Vec::new();
@@ -67,3 +70,5 @@ bootstrap!(ce4_missing_semicolon);
 bootstrap!(ce5_unexpected_array);
 bootstrap!(ce6_expected_array);
 bootstrap!(ce7_missing_parent);
+bootstrap!(ce7_missing_parent_database);
+bootstrap!(ce7_missing_parent_similar);
@@ -0,0 +1,8 @@
+class CfgVehicles {
+    class first: Car_F {
+        value = 1;
+    };
+    class second: Car_G {
+        value = 2;
+    };
+};
@@ -0,0 +1,7 @@
+class CfgVehicles {
+    class Car;
+    class Tank;
+    class abe_car: Cra {
+        value = 1;
+    };
+};
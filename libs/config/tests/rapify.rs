@@ -63,6 +63,7 @@ fn check(dir: &str) {
 bootstrap!(ace_main);
 bootstrap!(cba_multiline);
 bootstrap!(delete_class);
+bootstrap!(eval_exec);
 bootstrap!(external_class);
 bootstrap!(inheritence_array_extend);
 bootstrap!(join_digit);
@@ -70,5 +71,6 @@ bootstrap!(join_in_ident);
 bootstrap!(join);
 bootstrap!(nested_array);
 bootstrap!(numbers);
+bootstrap!(numbers_literals);
 bootstrap!(procedural_texture);
 bootstrap!(single_class);
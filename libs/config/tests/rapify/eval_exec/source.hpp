@@ -0,0 +1,8 @@
+#define SPACING 0.04
+__EXEC(_spacing = SPACING; _name = "rsc")
+class RscText {
+    x = __EVAL(_spacing * 2);
+    y = __EVAL(1/4);
+    name = __EVAL(_name + "_text");
+    text = "__EVAL(1 + 1)";
+};
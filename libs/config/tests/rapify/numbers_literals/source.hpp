@@ -0,0 +1,14 @@
+class numbers_literals {
+    hex = 0x1F;
+    hex_upper = 0XFF;
+    hex_negative = -0x10;
+    exponent = 1e5;
+    exponent_negative = 1e-05;
+    exponent_upper = 2.5E-07;
+    exponent_decimal = .5e-3;
+    leading_decimal = .25;
+    trailing_decimal = 5.;
+    positive = +3;
+    math = 1e-05 * 2;
+    array[] = {0x1F, -2.5E-3, 1e-05, .5, +4, {0X10, 3e2}};
+};
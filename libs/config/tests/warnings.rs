@@ -1,6 +1,6 @@
 use std::io::Read;
 
-use hemtt_common::reporting::Code;
+use hemtt_common::{project::ProjectConfig, reporting::Code};
 use hemtt_preprocessor::Processor;
 
 const ROOT: &str = "tests/warnings/";
@@ -24,7 +24,14 @@ fn check(dir: &str) {
         .unwrap();
     let source = workspace.join("source.hpp").unwrap();
     let processed = Processor::run(&source).unwrap();
-    let parsed = hemtt_config::parse(None, &processed);
+    let database = std::fs::read_to_string(folder.join("classes.json"))
+        .ok()
+        .map(|json| hemtt_config::ClassDatabase::from_json(&json).unwrap());
+    let project = folder
+        .join("project.toml")
+        .exists()
+        .then(|| ProjectConfig::from_file(&folder.join("project.toml")).unwrap());
+    let parsed = hemtt_config::parse_with_database(project.as_ref(), &processed, database.as_ref());
     match parsed {
         Ok(config) => {
             let mut expected = Vec::new();
@@ -60,3 +67,13 @@ fn check(dir: &str) {
 }
 
 bootstrap!(cw1_parent_case);
+bootstrap!(cw1_parent_case_suppress);
+bootstrap!(cw2_magwell_missing_magazine);
+bootstrap!(cw3_external_not_in_database);
+bootstrap!(cw4_required_addon_not_found);
+bootstrap!(cw5_delete_not_inherited);
+bootstrap!(cw6_deleted_redefined);
+bootstrap!(cw7_expand_not_inherited);
+bootstrap!(cw8_duplicate_selection);
+bootstrap!(cw9_animation_selection_missing);
+bootstrap!(cw10_unknown_skeleton);
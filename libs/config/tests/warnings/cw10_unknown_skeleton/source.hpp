@@ -0,0 +1,31 @@
+class CfgSkeletons {
+    class Default {
+        isDiscrete = 1;
+        skeletonInherit = "";
+        skeletonBones[] = {};
+    };
+    class abe_vehicle_skeleton;
+    class abe_car_skeleton: Default {
+        skeletonInherit = "Defualt";
+        skeletonBones[] = {
+            "wheel", ""
+        };
+    };
+};
+class CfgModels {
+    class Default {
+        sections[] = {};
+        skeletonName = "";
+    };
+    class abe_car: Default {
+        skeletonName = "abe_car_skeletn";
+    };
+    class abe_truck: Default {
+        skeletonName = "abe_vehicle_skeleton";
+        class Animations {
+            class wheel {
+                selection = "wheel";
+            };
+        };
+    };
+};
@@ -0,0 +1,8 @@
+class Imported;
+#pragma hemtt suppress cw1
+class Local: imported {
+    value = 1;
+};
+class Other: imported {
+    value = 2;
+};
@@ -0,0 +1,14 @@
+class CfgMagazineWells {
+    class abe_banana_shooter {
+        ADDON[] = {
+            "abe_cavendish",
+            "abe_plantain",
+            "abe_cavendis",
+            "external_banana"
+        };
+    };
+};
+class CfgMagazines {
+    class abe_cavendish {};
+    class abe_plantains {};
+};
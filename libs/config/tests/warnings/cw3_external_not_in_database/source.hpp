@@ -0,0 +1,10 @@
+class CfgVehicles {
+    class Car;
+    class Car_F: Car {
+        class Turrets;
+    };
+    class Cra_F;
+    class first: Car_F {
+        value = 1;
+    };
+};
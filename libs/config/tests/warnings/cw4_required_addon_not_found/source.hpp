@@ -0,0 +1,15 @@
+class CfgPatches {
+    class abe_main {
+        requiredAddons[] = {
+            "A3_Data_F",
+            "cba_mian",
+            "A3_Weapon_F"
+        };
+    };
+    class abe_other {
+        requiredAddons[] = {
+            "abe_main",
+            "abe_common"
+        };
+    };
+};
@@ -0,0 +1,15 @@
+class base {
+    class inner {};
+};
+class child: base {
+    delete inner;
+    delete other;
+};
+class external;
+class unknown: external {
+    delete anything;
+};
+class early: later {
+    delete anything;
+};
+class later {};
@@ -0,0 +1,6 @@
+class CfgVehicles {
+    delete old_car;
+    class old_car {
+        value = 1;
+    };
+};
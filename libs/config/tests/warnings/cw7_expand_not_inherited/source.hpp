@@ -0,0 +1,16 @@
+class base {
+    items[] = {"item1"};
+    value = 1;
+};
+class child: base {
+    items[] += {"item2"};
+    value[] += {"item3"};
+    other[] += {"item4"};
+};
+class solo {
+    items[] += {"item5"};
+};
+class external;
+class unknown: external {
+    items[] += {"item6"};
+};
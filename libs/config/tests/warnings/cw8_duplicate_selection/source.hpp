@@ -0,0 +1,20 @@
+class CfgSkeletons {
+    class abe_car_skeleton {
+        skeletonInherit = "";
+        skeletonBones[] = {
+            "wheel_1", "",
+            "wheel_2", "",
+            "Wheel_1", ""
+        };
+    };
+};
+class CfgModels {
+    class abe_car {
+        skeletonName = "abe_car_skeleton";
+        sections[] = {
+            "camo",
+            "light",
+            "camo"
+        };
+    };
+};
@@ -0,0 +1,42 @@
+class CfgSkeletons {
+    class abe_base_skeleton {
+        skeletonInherit = "";
+        skeletonBones[] = {
+            "wheel_1", ""
+        };
+    };
+    class abe_car_skeleton {
+        skeletonInherit = "abe_base_skeleton";
+        skeletonBones[] = {
+            "door", "",
+            "wheel_2", "wheel_1"
+        };
+    };
+};
+class CfgModels {
+    class abe_base {
+        skeletonName = "abe_car_skeleton";
+    };
+    class abe_car: abe_base {
+        class Animations {
+            class wheel_1 {
+                type = "rotation";
+                source = "wheel";
+                selection = "wheel_1";
+                axis = "wheel_1_axis";
+            };
+            class wheel_2 {
+                type = "rotation";
+                source = "wheel";
+                selection = "wheel_3";
+                axis = "wheel_2_axis";
+            };
+            class door {
+                type = "rotation";
+                source = "door";
+                selection = "Door";
+                axis = "door_axis";
+            };
+        };
+    };
+};
@@ -0,0 +1,61 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Arc, RwLock},
+};
+
+use hemtt_common::{reporting::Token, workspace::WorkspacePath};
+
+use crate::Error;
+
+type Files = HashMap<(String, u64), Arc<[Arc<Token>]>>;
+
+#[derive(Clone, Debug, Default)]
+/// Tokenized include files, shared between every file preprocessed in a build
+///
+/// Cloning the cache shares it, so it can be given to each thread
+pub struct IncludeCache {
+    files: Arc<RwLock<Files>>,
+}
+
+impl IncludeCache {
+    #[must_use]
+    /// Create a new empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// The number of files in the cache
+    ///
+    /// # Panics
+    /// If the cache was poisoned by a panic in another thread
+    pub fn len(&self) -> usize {
+        self.files.read().expect("include cache poisoned").len()
+    }
+
+    #[must_use]
+    /// Check if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Tokenize a file, reusing the tokens if the file was already tokenized with the same content
+    pub(crate) fn parse(&self, path: &WorkspacePath) -> Result<Vec<Arc<Token>>, Error> {
+        let source = path.read_to_string()?;
+        let key = (path.as_str().to_string(), {
+            let mut hasher = DefaultHasher::new();
+            source.hash(&mut hasher);
+            hasher.finish()
+        });
+        if let Some(tokens) = self.files.read().expect("include cache poisoned").get(&key) {
+            return Ok(tokens.to_vec());
+        }
+        let tokens = crate::parse::parse_str(&source, path)?;
+        self.files
+            .write()
+            .expect("include cache poisoned")
+            .insert(key, Arc::from(tokens.as_slice()));
+        Ok(tokens)
+    }
+}
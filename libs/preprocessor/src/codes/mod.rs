@@ -11,7 +11,10 @@ pub mod pe15_if_invalid_operator;
 pub mod pe16_if_incompatible_types;
 pub mod pe17_double_else;
 pub mod pe18_eoi_ifstate;
+pub mod pe19_pragma_unknown;
 pub mod pe1_unexpected_token;
+pub mod pe20_pragma_invalid_scope;
+pub mod pe21_eval_invalid;
 pub mod pe2_unexpected_eof;
 pub mod pe3_expected_ident;
 pub mod pe4_unknown_directive;
@@ -23,3 +26,5 @@ pub mod pe9_function_call_argument_count;
 
 pub mod pw1_redefine;
 pub mod pw2_invalid_config_case;
+pub mod pw3_unused_macro;
+pub mod pw4_unused_include;
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use ariadne::{ColorGenerator, Label, Report, ReportKind, Source};
 use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Token};
@@ -11,7 +11,7 @@ pub struct IncludeNotFound {
 }
 
 impl IncludeNotFound {
-    pub fn new(token: Vec<Rc<Token>>) -> Self {
+    pub fn new(token: Vec<Arc<Token>>) -> Self {
         Self {
             token: token.into_iter().map(|t| t.as_ref().clone()).collect(),
         }
@@ -29,7 +29,7 @@ impl Code for IfInvalidOperator {
     }
 
     fn help(&self) -> Option<String> {
-        Some("valid operators are ==, !=, <, >, <=, >=".to_string())
+        Some("valid operators are ==, !=, <, >, <=, >=, &&, ||, !, +, -, *, /, %".to_string())
     }
 
     fn report_generate(&self) -> Option<String> {
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use ariadne::{sources, ColorGenerator, Label, Report, ReportKind};
 use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Token};
@@ -17,9 +17,9 @@ pub struct IfIncompatibleType {
 
 impl IfIncompatibleType {
     pub fn new(
-        left: (Vec<Rc<Token>>, bool),
-        operator: Vec<Rc<Token>>,
-        right: (Vec<Rc<Token>>, bool),
+        left: (Vec<Arc<Token>>, bool),
+        operator: Vec<Arc<Token>>,
+        right: (Vec<Arc<Token>>, bool),
     ) -> Self {
         Self {
             left: (
@@ -0,0 +1,88 @@
+use ariadne::{ColorGenerator, Fmt, Label, Report, ReportKind, Source};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Token};
+use tracing::error;
+
+#[allow(unused)]
+/// Invalid scope for a `#pragma hemtt` command
+pub struct PragmaInvalidScope {
+    /// The [`Token`] of the scope
+    pub(crate) token: Box<Token>,
+}
+
+impl Code for PragmaInvalidScope {
+    fn ident(&self) -> &'static str {
+        "PE20"
+    }
+
+    fn token(&self) -> Option<&Token> {
+        Some(&self.token)
+    }
+
+    fn message(&self) -> String {
+        "invalid `#pragma hemtt` scope".to_string()
+    }
+
+    fn label_message(&self) -> String {
+        format!(
+            "unknown scope `{}`",
+            self.token.symbol().to_string().replace('\n', "\\n")
+        )
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("valid scopes are `line`, `file` and `include`".to_string())
+    }
+
+    fn report_generate(&self) -> Option<String> {
+        let mut colors = ColorGenerator::default();
+        let a = colors.next();
+        let mut out = Vec::new();
+        let span = self.token.position().span();
+        let report = Report::build(
+            ReportKind::Error,
+            self.token.position().path().as_str(),
+            span.start,
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((self.token.position().path().as_str(), span.start..span.end))
+                .with_color(a)
+                .with_message(format!(
+                    "unknown scope `{}`",
+                    self.token.symbol().to_string().fg(a)
+                )),
+        )
+        .with_help(format!(
+            "valid scopes are `{}`, `{}` and `{}`",
+            "line".fg(a),
+            "file".fg(a),
+            "include".fg(a)
+        ));
+        if let Err(e) = report.finish().write_for_stdout(
+            (
+                self.token.position().path().as_str(),
+                Source::from(
+                    self.token
+                        .position()
+                        .path()
+                        .read_to_string()
+                        .unwrap_or_default(),
+                ),
+            ),
+            &mut out,
+        ) {
+            error!("while reporting: {e}");
+            return None;
+        }
+        Some(String::from_utf8(out).unwrap_or_default())
+    }
+
+    fn ci_generate(&self) -> Vec<Annotation> {
+        vec![self.annotation(
+            AnnotationLevel::Error,
+            self.token.position().path().as_str().to_string(),
+            self.token.position(),
+        )]
+    }
+}
@@ -0,0 +1,87 @@
+use ariadne::{ColorGenerator, Fmt, Label, Report, ReportKind, Source};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Token};
+use tracing::error;
+
+#[allow(unused)]
+/// An `__EVAL` or `__EXEC` that could not be evaluated
+pub struct EvalInvalid {
+    /// The `__EVAL` or `__EXEC` [`Token`]
+    pub(crate) token: Box<Token>,
+    /// The expression after macros were expanded
+    pub(crate) expression: String,
+}
+
+impl Code for EvalInvalid {
+    fn ident(&self) -> &'static str {
+        "PE21"
+    }
+
+    fn token(&self) -> Option<&Token> {
+        Some(&self.token)
+    }
+
+    fn message(&self) -> String {
+        format!("unable to evaluate `{}`", self.token.symbol().to_string())
+    }
+
+    fn label_message(&self) -> String {
+        format!("could not evaluate `{}`", self.expression.trim())
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "only arithmetic, strings and variables assigned by `__EXEC` are supported".to_string(),
+        )
+    }
+
+    fn report_generate(&self) -> Option<String> {
+        let mut colors = ColorGenerator::default();
+        let a = colors.next();
+        let mut out = Vec::new();
+        let span = self.token.position().span();
+        let report = Report::build(
+            ReportKind::Error,
+            self.token.position().path().as_str(),
+            span.start,
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((self.token.position().path().as_str(), span.start..span.end))
+                .with_color(a)
+                .with_message(format!(
+                    "could not evaluate `{}`",
+                    self.expression.trim().fg(a)
+                )),
+        )
+        .with_help(format!(
+            "only arithmetic, strings and variables assigned by `{}` are supported",
+            "__EXEC".fg(a)
+        ));
+        if let Err(e) = report.finish().write_for_stdout(
+            (
+                self.token.position().path().as_str(),
+                Source::from(
+                    self.token
+                        .position()
+                        .path()
+                        .read_to_string()
+                        .unwrap_or_default(),
+                ),
+            ),
+            &mut out,
+        ) {
+            error!("while reporting: {e}");
+            return None;
+        }
+        Some(String::from_utf8(out).unwrap_or_default())
+    }
+
+    fn ci_generate(&self) -> Vec<Annotation> {
+        vec![self.annotation(
+            AnnotationLevel::Error,
+            self.token.position().path().as_str().to_string(),
+            self.token.position(),
+        )]
+    }
+}
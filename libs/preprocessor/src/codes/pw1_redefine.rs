@@ -7,8 +7,8 @@ use tracing::error;
 pub struct RedefineMacro {
     /// The [`Token`] that was defined
     pub(crate) token: Box<Token>,
-    /// The original [`Token`] that was defined
-    pub(crate) original: Box<Token>,
+    /// The original [`Token`] that was defined, [`None`] if it was defined by the workspace
+    pub(crate) original: Option<Box<Token>>,
 }
 
 impl Code for RedefineMacro {
@@ -41,7 +41,7 @@ impl Code for RedefineMacro {
         let color_original = colors.next();
         let mut out = Vec::new();
         let span = self.token.position().span();
-        if let Err(e) = Report::build(
+        let mut report = Report::build(
             ReportKind::Warning,
             self.token.position().path().as_str(),
             span.start,
@@ -56,35 +56,30 @@ impl Code for RedefineMacro {
             .with_color(color_token)
             .with_message("redefining macro"),
         )
-        .with_label(
-            Label::new((
-                self.original.position().path().to_string(),
-                self.original.position().start().0..self.original.position().end().0,
-            ))
-            .with_color(color_original)
-            .with_message("previous definition here"),
-        )
-        .with_help("`#undef` macros before redefining them")
-        .finish()
-        .write_for_stdout(
-            sources(vec![
-                (
-                    self.token.position().path().to_string(),
-                    self.token
-                        .position()
-                        .path()
-                        .read_to_string()
-                        .unwrap_or_default(),
-                ),
-                (
-                    self.original.position().path().to_string(),
-                    self.original
-                        .position()
-                        .path()
-                        .read_to_string()
-                        .unwrap_or_default(),
-                ),
-            ]),
+        .with_help("`#undef` macros before redefining them");
+        let mut files = vec![self.token.position().path()];
+        if let Some(original) = &self.original {
+            report = report.with_label(
+                Label::new((
+                    original.position().path().to_string(),
+                    original.position().start().0..original.position().end().0,
+                ))
+                .with_color(color_original)
+                .with_message("previous definition here"),
+            );
+            files.push(original.position().path());
+        } else {
+            report = report.with_note(format!(
+                "`{}` is defined by the project or on the command line",
+                self.token.symbol().to_string()
+            ));
+        }
+        if let Err(e) = report.finish().write_for_stdout(
+            sources(
+                files
+                    .into_iter()
+                    .map(|path| (path.to_string(), path.read_to_string().unwrap_or_default())),
+            ),
             &mut out,
         ) {
             error!("while reporting: {e}");
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use ariadne::{sources, ColorGenerator, Label, Report, ReportKind};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Token};
+use tracing::error;
+
+/// A macro is defined but never used
+pub struct UnusedMacro {
+    /// The name of the macro at its definition
+    pub(crate) token: Arc<Token>,
+}
+
+impl Code for UnusedMacro {
+    fn ident(&self) -> &'static str {
+        "PW3"
+    }
+
+    fn token(&self) -> Option<&Token> {
+        Some(&self.token)
+    }
+
+    fn message(&self) -> String {
+        "unused macro".to_string()
+    }
+
+    fn label_message(&self) -> String {
+        format!(
+            "macro `{}` is never used",
+            self.token.symbol().to_string().replace('\n', "\\n")
+        )
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(
+            "remove the macro, or suppress the warning with `#pragma hemtt suppress pw3`"
+                .to_string(),
+        )
+    }
+
+    fn report_generate(&self) -> Option<String> {
+        let mut colors = ColorGenerator::default();
+        let mut out = Vec::new();
+        let span = self.token.position().span();
+        if let Err(e) = Report::build(
+            ReportKind::Warning,
+            self.token.position().path().as_str(),
+            span.start,
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                self.token.position().path().to_string(),
+                span.start..span.end,
+            ))
+            .with_color(colors.next())
+            .with_message(self.label_message()),
+        )
+        .with_help(self.help().expect("help should be Some"))
+        .finish()
+        .write_for_stdout(
+            sources(vec![(
+                self.token.position().path().to_string(),
+                self.token
+                    .position()
+                    .path()
+                    .read_to_string()
+                    .unwrap_or_default(),
+            )]),
+            &mut out,
+        ) {
+            error!("while reporting: {e}");
+            return None;
+        }
+        Some(String::from_utf8(out).unwrap_or_default())
+    }
+
+    fn ci_generate(&self) -> Vec<Annotation> {
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            self.token.position().path().as_str().to_string(),
+            self.token.position(),
+        )]
+    }
+}
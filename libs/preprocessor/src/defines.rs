@@ -1,4 +1,4 @@
-use std::{collections::HashMap, rc::Rc, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 use hemtt_common::{
     position::Position,
@@ -8,7 +8,7 @@ use strsim::levenshtein;
 
 use crate::definition::Definition;
 
-type InnerDefines = HashMap<Arc<str>, (Rc<Token>, Definition)>;
+type InnerDefines = HashMap<Arc<str>, (Arc<Token>, Definition)>;
 
 #[derive(Clone, Default)]
 /// `HashMap` of all current defines
@@ -25,29 +25,36 @@ const BUILTIN_CONST: [(&str, u8); 2] = [("__ARMA__", 1), ("__ARMA3__", 1)];
 /// Built-in macros that HEMTT supports, generated by the preprocessor
 const BUILTIN_GEN: [&str; 4] = ["__COUNTER__", "__COUNTER_RESET__", "__FILE__", "__LINE__"];
 
-/// Built-in macros that HEMTT intentionally does not support
-const BUILTIN_PROTEST: [&str; 16] = [
+/// Built-in macros that HEMTT supports, evaluated by the preprocessor
+const BUILTIN_EVAL: [&str; 2] = ["__EVAL", "__EXEC"];
+
+/// Built-in macros that HEMTT supports when enabled by the project, defined with the workspace
+const BUILTIN_PROJECT: [&str; 10] = [
     "__DATE_ARR__",
     "__DATE_STR__",
     "__DATE_STR_ISO8601__",
     "__TIME__",
     "__TIME_UTC__",
     "__TIMESTAMP_UTC__",
-    "__RAND_INT*__",
-    "__RAND_UINT*__",
     "__GAME_VER__",
     "__GAME_VER_MAJ__",
     "__GAME_VER_MIN__",
     "__GAME_BUILD__",
+];
+
+/// Built-in macros that HEMTT intentionally does not support
+const BUILTIN_PROTEST: [&str; 4] = [
+    "__RAND_INT*__",
+    "__RAND_UINT*__",
     "__A3_DIAG__",
     "__A3_DEBUG__",
-    "__EXEC",
-    "__EVAL",
 ];
 
 impl Defines {
     pub fn is_builtin(key: &str) -> bool {
         BUILTIN_GEN.contains(&key)
+            || BUILTIN_EVAL.contains(&key)
+            || BUILTIN_PROJECT.contains(&key)
             || BUILTIN_PROTEST.contains(&key)
             || BUILTIN_CONST.iter().any(|(k, _)| *k == key)
     }
@@ -69,9 +76,9 @@ impl Defines {
 
     pub fn get_with_gen(
         &mut self,
-        key: &Rc<Token>,
+        key: &Arc<Token>,
         site: Option<&Position>,
-    ) -> Option<(Rc<Token>, Definition)> {
+    ) -> Option<(Arc<Token>, Definition)> {
         let ident = key.to_string();
         if let Some(site) = site {
             if BUILTIN_GEN.contains(&ident.as_str()) {
@@ -81,7 +88,7 @@ impl Defines {
                         self.counter += 1;
                         return Some((
                             key.clone(),
-                            Definition::Value(vec![Rc::new(Token::new(
+                            Definition::Value(vec![Arc::new(Token::new(
                                 Symbol::Digit(counter.into()),
                                 key.position().clone(),
                             ))]),
@@ -95,8 +102,8 @@ impl Defines {
                         return Some((
                             key.clone(),
                             Definition::Value(vec![
-                                Rc::new(Token::new(Symbol::DoubleQuote, key.position().clone())),
-                                Rc::new(site.path().workspace().project().map_or_else(
+                                Arc::new(Token::new(Symbol::DoubleQuote, key.position().clone())),
+                                Arc::new(site.path().workspace().project().map_or_else(
                                     || {
                                         Token::new(
                                             Symbol::Word(site.path().as_str().to_string()),
@@ -114,14 +121,14 @@ impl Defines {
                                         )
                                     },
                                 )),
-                                Rc::new(Token::new(Symbol::DoubleQuote, key.position().clone())),
+                                Arc::new(Token::new(Symbol::DoubleQuote, key.position().clone())),
                             ]),
                         ));
                     }
                     "__LINE__" => {
                         return Some((
                             key.clone(),
-                            Definition::Value(vec![Rc::new(Token::new(
+                            Definition::Value(vec![Arc::new(Token::new(
                                 Symbol::Digit(site.start().1 .0),
                                 key.position().clone(),
                             ))]),
@@ -150,7 +157,7 @@ impl Defines {
         ret
     }
 
-    pub fn get_readonly(&self, key: &str) -> Option<(Rc<Token>, Definition)> {
+    pub fn get_readonly(&self, key: &str) -> Option<(Arc<Token>, Definition)> {
         self.stack
             .last()
             .as_ref()
@@ -160,7 +167,7 @@ impl Defines {
     }
 
     #[cfg(test)]
-    pub fn get_test(&self, key: &str) -> Option<&(Rc<Token>, Definition)> {
+    pub fn get_test(&self, key: &str) -> Option<&(Arc<Token>, Definition)> {
         self.stack
             .last()
             .as_ref()
@@ -171,8 +178,8 @@ impl Defines {
     pub fn insert(
         &mut self,
         key: &str,
-        value: (Rc<Token>, Definition),
-    ) -> Option<(Rc<Token>, Definition)> {
+        value: (Arc<Token>, Definition),
+    ) -> Option<(Arc<Token>, Definition)> {
         if let Some(stack) = self.stack.last_mut() {
             stack.1.insert(Arc::from(key), value)
         } else {
@@ -180,7 +187,7 @@ impl Defines {
         }
     }
 
-    pub fn remove(&mut self, key: &str) -> Option<(Rc<Token>, Definition)> {
+    pub fn remove(&mut self, key: &str) -> Option<(Arc<Token>, Definition)> {
         if let Some(scope) = self.stack.last_mut() {
             scope.1.remove(key)
         } else {
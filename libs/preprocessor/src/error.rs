@@ -10,6 +10,9 @@ pub enum Error {
     #[error("Coded error")]
     /// A coded error
     Code(Box<dyn Code>),
+    #[error("{} coded errors", .0.len())]
+    /// Several coded errors, from a file that was preprocessed past its first error
+    Codes(Vec<Box<dyn Code>>),
     #[error("IO Error: {0}")]
     /// [`std::io::Error`]
     Io(Box<std::io::Error>),
@@ -43,8 +46,18 @@ impl Error {
     pub fn get_code(&self) -> Option<Box<&dyn Code>> {
         match self {
             Self::Code(c) => Some(Box::new(&**c)),
+            Self::Codes(c) => c.first().map(|c| Box::new(&**c)),
             Self::Reporting(e) => e.get_code(),
             Self::Io(_) | Self::Pest(_) | Self::Workspace(_) => None,
         }
     }
+
+    #[must_use]
+    /// Every coded error, in the order they were found
+    pub fn get_codes(&self) -> Vec<&dyn Code> {
+        match self {
+            Self::Codes(c) => c.iter().map(|c| &**c).collect(),
+            _ => self.get_code().map(|c| *c).into_iter().collect(),
+        }
+    }
 }
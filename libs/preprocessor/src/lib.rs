@@ -3,6 +3,7 @@
 
 //! HEMTT - Arma 3 Preprocessor
 
+mod cache;
 mod codes;
 mod defines;
 mod definition;
@@ -10,6 +11,17 @@ mod error;
 mod ifstate;
 mod parse;
 mod processor;
+mod resolver;
+mod unused;
 
+pub use cache::IncludeCache;
 pub use error::Error;
 pub use processor::Processor;
+pub use resolver::{IncludeResolver, NoIncludes};
+pub use unused::Unused;
+
+#[must_use]
+/// Checks if a name is a built-in macro, which can not be defined or changed
+pub fn is_builtin(name: &str) -> bool {
+    defines::Defines::is_builtin(name)
+}
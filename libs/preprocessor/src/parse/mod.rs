@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use hemtt_common::{
     position::{LineCol, Position},
@@ -22,9 +22,22 @@ pub struct PreprocessorParser;
 ///
 /// # Panics
 /// If the file is invalid
-pub fn parse(path: &WorkspacePath) -> Result<Vec<Rc<Token>>, Error> {
+pub fn parse(path: &WorkspacePath) -> Result<Vec<Arc<Token>>, Error> {
     let source = path.read_to_string()?;
-    let pairs = PreprocessorParser::parse(Rule::file, &source)?;
+    parse_str(&source, path)
+}
+
+/// Parse source that is not read from a file into tokens, such as a define from the project
+///
+/// The tokens are positioned as if `source` was at the start of `path`
+///
+/// # Errors
+/// If the source is invalid
+///
+/// # Panics
+/// If the source is invalid
+pub fn parse_str(source: &str, path: &WorkspacePath) -> Result<Vec<Arc<Token>>, Error> {
+    let pairs = PreprocessorParser::parse(Rule::file, source)?;
     let mut tokens = Vec::new();
     let mut line = 1;
     let mut col = 0;
@@ -44,7 +57,7 @@ pub fn parse(path: &WorkspacePath) -> Result<Vec<Rc<Token>>, Error> {
             Rule::COMMENT => {
                 if in_string {
                     if !skipping_comment {
-                        tokens.push(Rc::new(Token::new(
+                        tokens.push(Arc::new(Token::new(
                             Symbol::Word(pair.as_str().to_string()),
                             Position::new(
                                 start,
@@ -83,7 +96,7 @@ pub fn parse(path: &WorkspacePath) -> Result<Vec<Rc<Token>>, Error> {
             continue;
         }
         let end = LineCol(offset, (line, col));
-        tokens.push(Rc::new(Token::new(
+        tokens.push(Arc::new(Token::new(
             Symbol::to_symbol(pair),
             Position::new(start, end, path.clone()),
         )));
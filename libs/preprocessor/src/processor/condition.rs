@@ -0,0 +1,531 @@
+//! `#if` expressions
+
+use std::sync::Arc;
+
+use hemtt_common::reporting::{Symbol, Token};
+use peekmore::{PeekMore, PeekMoreIterator};
+
+use crate::{
+    codes::{
+        pe15_if_invalid_operator::IfInvalidOperator,
+        pe16_if_incompatible_types::IfIncompatibleType, pe1_unexpected_token::UnexpectedToken,
+        pe2_unexpected_eof::UnexpectedEOF, pe7_if_unit_or_function::IfUnitOrFunction,
+        pe8_if_undefined::IfUndefined,
+    },
+    defines::Defines,
+    definition::Definition,
+    Error,
+};
+
+use super::Processor;
+
+/// How deep macros are expanded inside an `#if`
+const MAX_DEPTH: usize = 32;
+
+/// Binary operators, from lowest to highest precedence
+const BINARY: [&[&str]; 6] = [
+    &["||"],
+    &["&&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+const UNARY: [&str; 3] = ["!", "-", "+"];
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Number(f64),
+    Text(String),
+}
+
+#[derive(Clone)]
+struct Operand {
+    value: Value,
+    /// The text as written, used by `==` and `!=`
+    text: String,
+    tokens: Vec<Arc<Token>>,
+    /// The operand came from a macro
+    defined: bool,
+    /// The operand is an identifier that is not defined
+    undefined: bool,
+}
+
+impl Operand {
+    fn number(value: f64, tokens: Vec<Arc<Token>>) -> Self {
+        Self {
+            value: Value::Number(value),
+            text: value.to_string(),
+            tokens,
+            defined: false,
+            undefined: false,
+        }
+    }
+
+    fn boolean(value: bool, tokens: Vec<Arc<Token>>) -> Self {
+        Self::number(if value { 1.0 } else { 0.0 }, tokens)
+    }
+
+    const fn as_number(&self) -> Option<f64> {
+        match self.value {
+            Value::Number(n) => Some(n),
+            Value::Text(_) => None,
+        }
+    }
+
+    fn truthy(&self, defines: &Defines) -> Result<bool, Error> {
+        if self.undefined {
+            return Err(Error::Code(Box::new(IfUndefined::new(
+                Box::new(self.tokens[0].as_ref().clone()),
+                defines,
+            ))));
+        }
+        Ok(self.as_number().is_some_and(|n| n != 0.0))
+    }
+}
+
+enum Item {
+    Operand(Operand),
+    Operator(String, Vec<Arc<Token>>),
+    Open(Arc<Token>),
+    Close(Arc<Token>),
+}
+
+enum Expr {
+    Operand(Operand),
+    Unary(String, Vec<Arc<Token>>, Box<Self>),
+    Binary(String, Vec<Arc<Token>>, Box<Self>, Box<Self>),
+}
+
+impl Processor {
+    pub(crate) fn directive_if(
+        &mut self,
+        command: Arc<Token>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Result<(), Error> {
+        let items = self.condition_items(stream, false, 0)?;
+        let end = stream.peek().cloned().unwrap_or_else(|| command.clone());
+        if items.is_empty() {
+            return Err(Error::Code(Box::new(UnexpectedEOF {
+                token: Box::new(end.as_ref().clone()),
+            })));
+        }
+        let mut parser = ConditionParser {
+            items: &items,
+            pos: 0,
+            end,
+        };
+        let expr = parser.parse()?;
+        let read = evaluate(&expr, &self.defines)?.truthy(&self.defines)?;
+        self.ifstates.push_if(command, read);
+        Self::expect_nothing_to_newline(stream)
+    }
+
+    /// Read the items of an `#if` up to the end of the line, expanding macros
+    fn condition_items(
+        &mut self,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+        defined: bool,
+        depth: usize,
+    ) -> Result<Vec<Item>, Error> {
+        let mut items = Vec::new();
+        while let Some(token) = stream.peek().cloned() {
+            match token.symbol() {
+                Symbol::Newline | Symbol::Eoi => break,
+                Symbol::Escape if stream.peek_nth(1).is_some_and(|t| t.symbol().is_newline()) => {
+                    stream.next();
+                    stream.next();
+                }
+                Symbol::Whitespace(_) | Symbol::Comment(_) => {
+                    stream.next();
+                }
+                Symbol::LeftParenthesis => {
+                    stream.next();
+                    items.push(Item::Open(token));
+                }
+                Symbol::RightParenthesis => {
+                    stream.next();
+                    items.push(Item::Close(token));
+                }
+                Symbol::Digit(_) => items.push(number(stream, defined)),
+                Symbol::DoubleQuote => items.push(string(stream, defined)?),
+                Symbol::Word(w) if w == "defined" => {
+                    stream.next();
+                    self.skip_whitespace(stream, None);
+                    let open = stream
+                        .peek()
+                        .is_some_and(|t| t.symbol() == &Symbol::LeftParenthesis);
+                    if open {
+                        stream.next();
+                    }
+                    let name = self.next_word(stream, None)?;
+                    if open {
+                        self.skip_whitespace(stream, None);
+                        Self::expect_symbol(stream, &Symbol::RightParenthesis, ")")?;
+                    }
+                    self.use_macro(&name.symbol().to_string());
+                    items.push(Item::Operand(Operand {
+                        defined,
+                        ..Operand::boolean(
+                            self.defines.contains_key(&name.symbol().to_string()),
+                            vec![token, name],
+                        )
+                    }));
+                }
+                Symbol::Word(w) if w == "__has_include" => {
+                    let found = self.has_include(stream)?;
+                    items.push(Item::Operand(Operand {
+                        defined,
+                        ..Operand::boolean(found, vec![token])
+                    }));
+                }
+                Symbol::Word(w) => {
+                    stream.next();
+                    if depth < MAX_DEPTH && self.defines.contains_key(w) {
+                        let Some((source, definition)) =
+                            self.defines.get_with_gen(&token, Some(token.position()))
+                        else {
+                            unreachable!("contains_key was checked");
+                        };
+                        self.used.insert(source.position().clone());
+                        let Definition::Value(tokens) = definition else {
+                            return Err(Error::Code(Box::new(IfUnitOrFunction::new(
+                                Box::new(token.as_ref().clone()),
+                                &self.defines.clone(),
+                            ))));
+                        };
+                        items.extend(self.condition_items(
+                            &mut tokens.into_iter().peekmore(),
+                            true,
+                            depth + 1,
+                        )?);
+                    } else {
+                        items.push(Item::Operand(Operand {
+                            value: Value::Text(w.clone()),
+                            text: w.clone(),
+                            tokens: vec![token],
+                            defined,
+                            undefined: true,
+                        }));
+                    }
+                }
+                _ => items.extend(operators(stream)?),
+            }
+        }
+        Ok(items)
+    }
+
+    /// `__has_include("path")` in an `#if`
+    ///
+    /// The path is located the same way as `#include`
+    fn has_include(
+        &mut self,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Result<bool, Error> {
+        stream.next();
+        self.skip_whitespace(stream, None);
+        Self::expect_symbol(stream, &Symbol::LeftParenthesis, "(")?;
+        self.skip_whitespace(stream, None);
+        let path = Self::include_path(stream)?;
+        self.skip_whitespace(stream, None);
+        Self::expect_symbol(stream, &Symbol::RightParenthesis, ")")?;
+        Ok(self
+            .locate(&path.iter().map(|t| t.to_string()).collect::<String>())?
+            .is_some())
+    }
+
+    fn expect_symbol(
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+        symbol: &Symbol,
+        expected: &str,
+    ) -> Result<(), Error> {
+        let token = stream
+            .next()
+            .expect("stream should always end with an eoi token");
+        if token.symbol() == symbol {
+            return Ok(());
+        }
+        if token.symbol().is_eoi() {
+            return Err(Error::Code(Box::new(UnexpectedEOF {
+                token: Box::new(token.as_ref().clone()),
+            })));
+        }
+        Err(Error::Code(Box::new(UnexpectedToken {
+            token: Box::new(token.as_ref().clone()),
+            expected: vec![expected.to_string()],
+        })))
+    }
+}
+
+/// A number, such as `2` or `1.5`
+fn number(stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>, defined: bool) -> Item {
+    let mut tokens = Vec::new();
+    while let Some(token) = stream.peek() {
+        if !matches!(token.symbol(), Symbol::Digit(_)) && token.symbol().to_string() != "." {
+            break;
+        }
+        tokens.push(stream.next().expect("was peeked"));
+    }
+    let text = tokens.iter().map(|t| t.to_string()).collect::<String>();
+    Item::Operand(Operand {
+        value: text
+            .parse()
+            .map_or_else(|_| Value::Text(text.clone()), Value::Number),
+        text,
+        tokens,
+        defined,
+        undefined: false,
+    })
+}
+
+/// A quoted string, it must end on the same line
+fn string(
+    stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    defined: bool,
+) -> Result<Item, Error> {
+    let mut tokens = vec![stream.next().expect("was peeked")];
+    loop {
+        // macro values do not end with an eoi token
+        let token = match stream.next() {
+            Some(token) if !token.symbol().is_newline() && !token.symbol().is_eoi() => token,
+            token => {
+                return Err(Error::Code(Box::new(UnexpectedEOF {
+                    token: Box::new(
+                        token
+                            .as_ref()
+                            .unwrap_or_else(|| tokens.last().expect("has quote"))
+                            .as_ref()
+                            .clone(),
+                    ),
+                })));
+            }
+        };
+        let closed = token.symbol().is_double_quote();
+        tokens.push(token);
+        if closed {
+            break;
+        }
+    }
+    let text = tokens.iter().map(|t| t.to_string()).collect::<String>();
+    Ok(Item::Operand(Operand {
+        value: Value::Text(text[1..text.len() - 1].to_string()),
+        text,
+        tokens,
+        defined,
+        undefined: false,
+    }))
+}
+
+/// A run of operator characters, such as `&&!`
+fn operators(
+    stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+) -> Result<Vec<Item>, Error> {
+    let mut tokens = Vec::new();
+    while let Some(token) = stream.peek().cloned() {
+        let continuation = token.symbol().is_escape()
+            && stream.peek_nth(1).is_some_and(|t| t.symbol().is_newline());
+        if continuation
+            || token.symbol().is_word()
+            || token.symbol().is_whitespace()
+            || token.symbol().is_comment()
+            || token.symbol().is_newline()
+            || token.symbol().is_eoi()
+            || token.symbol().is_double_quote()
+            || matches!(
+                token.symbol(),
+                Symbol::Digit(_) | Symbol::LeftParenthesis | Symbol::RightParenthesis
+            )
+        {
+            break;
+        }
+        tokens.push(stream.next().expect("was peeked"));
+    }
+    Ok(split_operators(&tokens)
+        .ok_or_else(|| {
+            Error::Code(Box::new(IfInvalidOperator {
+                tokens: tokens.iter().map(|t| t.as_ref().clone()).collect(),
+            }))
+        })?
+        .into_iter()
+        .map(|(op, tokens)| Item::Operator(op, tokens))
+        .collect())
+}
+
+/// Split a run of operator characters, such as `&&!`, into operators
+///
+/// Returns `None` if the run contains an invalid operator
+fn split_operators(tokens: &[Arc<Token>]) -> Option<Vec<(String, Vec<Arc<Token>>)>> {
+    let mut operators = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        let pair = tokens
+            .get(i..i + 2)
+            .map(|pair| pair.iter().map(|t| t.to_string()).collect::<String>());
+        if let Some(pair) = pair.filter(|pair| {
+            BINARY
+                .iter()
+                .any(|ops| ops.contains(&pair.as_str()) && pair.len() == 2)
+        }) {
+            operators.push((pair, tokens[i..i + 2].to_vec()));
+            i += 2;
+            continue;
+        }
+        let single = tokens[i].to_string();
+        if !BINARY.iter().any(|ops| ops.contains(&single.as_str()))
+            && !UNARY.contains(&single.as_str())
+        {
+            return None;
+        }
+        operators.push((single, vec![tokens[i].clone()]));
+        i += 1;
+    }
+    Some(operators)
+}
+
+struct ConditionParser<'a> {
+    items: &'a [Item],
+    pos: usize,
+    /// The token after the condition, for reporting a missing value
+    end: Arc<Token>,
+}
+
+impl ConditionParser<'_> {
+    fn parse(&mut self) -> Result<Expr, Error> {
+        let expr = self.binary(0)?;
+        match self.items.get(self.pos) {
+            None => Ok(expr),
+            Some(Item::Operand(operand)) => Err(unexpected(&operand.tokens[0], "operator")),
+            Some(Item::Operator(_, tokens)) => Err(unexpected(&tokens[0], "value")),
+            Some(Item::Open(token) | Item::Close(token)) => Err(unexpected(token, "operator")),
+        }
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, Error> {
+        if level == BINARY.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let Some(Item::Operator(op, tokens)) = self.items.get(self.pos) {
+            if !BINARY[level].contains(&op.as_str()) {
+                break;
+            }
+            self.pos += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op.clone(), tokens.clone(), Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        if let Some(Item::Operator(op, tokens)) = self.items.get(self.pos) {
+            if UNARY.contains(&op.as_str()) {
+                self.pos += 1;
+                let operand = self.unary()?;
+                return Ok(Expr::Unary(op.clone(), tokens.clone(), Box::new(operand)));
+            }
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, Error> {
+        let item = self.items.get(self.pos);
+        self.pos += 1;
+        match item {
+            Some(Item::Operand(operand)) => Ok(Expr::Operand(operand.clone())),
+            Some(Item::Open(_)) => {
+                let expr = self.binary(0)?;
+                match self.items.get(self.pos) {
+                    Some(Item::Close(_)) => {
+                        self.pos += 1;
+                        Ok(expr)
+                    }
+                    Some(Item::Operand(operand)) => Err(unexpected(&operand.tokens[0], ")")),
+                    Some(Item::Operator(_, tokens)) => Err(unexpected(&tokens[0], ")")),
+                    Some(Item::Open(token)) => Err(unexpected(token, ")")),
+                    None => Err(unexpected(&self.end, ")")),
+                }
+            }
+            Some(Item::Operator(_, tokens)) => Err(unexpected(&tokens[0], "value")),
+            Some(Item::Close(token)) => Err(unexpected(token, "value")),
+            None => Err(unexpected(&self.end, "value")),
+        }
+    }
+}
+
+fn unexpected(token: &Token, expected: &str) -> Error {
+    if token.symbol().is_eoi() {
+        return Error::Code(Box::new(UnexpectedEOF {
+            token: Box::new(token.clone()),
+        }));
+    }
+    Error::Code(Box::new(UnexpectedToken {
+        token: Box::new(token.clone()),
+        expected: vec![expected.to_string()],
+    }))
+}
+
+/// Evaluate an expression, `&&` and `||` only evaluate their right side when needed
+fn evaluate(expr: &Expr, defines: &Defines) -> Result<Operand, Error> {
+    match expr {
+        Expr::Operand(operand) => Ok(operand.clone()),
+        Expr::Unary(op, tokens, operand) => {
+            let operand = evaluate(operand, defines)?;
+            if op == "!" {
+                return Ok(Operand::boolean(!operand.truthy(defines)?, tokens.clone()));
+            }
+            let Some(value) = operand.as_number() else {
+                return Err(Error::Code(Box::new(IfIncompatibleType::new(
+                    (tokens.clone(), false),
+                    tokens.clone(),
+                    (operand.tokens, operand.defined),
+                ))));
+            };
+            Ok(Operand::number(
+                if op == "-" { -value } else { value },
+                tokens.clone(),
+            ))
+        }
+        Expr::Binary(op, tokens, left, right) => {
+            let left = evaluate(left, defines)?;
+            match op.as_str() {
+                "&&" | "||" => {
+                    let left = left.truthy(defines)?;
+                    if left == (op == "||") {
+                        return Ok(Operand::boolean(left, tokens.clone()));
+                    }
+                    let right = evaluate(right, defines)?.truthy(defines)?;
+                    return Ok(Operand::boolean(right, tokens.clone()));
+                }
+                _ => {}
+            }
+            let right = evaluate(right, defines)?;
+            if op == "==" || op == "!=" {
+                let equal = match (left.as_number(), right.as_number()) {
+                    (Some(l), Some(r)) => (l - r).abs() < f64::EPSILON,
+                    _ => left.text == right.text,
+                };
+                return Ok(Operand::boolean(equal == (op == "=="), tokens.clone()));
+            }
+            let (Some(l), Some(r)) = (left.as_number(), right.as_number()) else {
+                return Err(Error::Code(Box::new(IfIncompatibleType::new(
+                    (left.tokens, left.defined),
+                    tokens.clone(),
+                    (right.tokens, right.defined),
+                ))));
+            };
+            Ok(match op.as_str() {
+                "<" => Operand::boolean(l < r, tokens.clone()),
+                ">" => Operand::boolean(l > r, tokens.clone()),
+                "<=" => Operand::boolean(l <= r, tokens.clone()),
+                ">=" => Operand::boolean(l >= r, tokens.clone()),
+                "+" => Operand::number(l + r, tokens.clone()),
+                "-" => Operand::number(l - r, tokens.clone()),
+                "*" => Operand::number(l * r, tokens.clone()),
+                "/" => Operand::number(l / r, tokens.clone()),
+                "%" => Operand::number(l % r, tokens.clone()),
+                _ => unreachable!("only valid operators are parsed"),
+            })
+        }
+    }
+}
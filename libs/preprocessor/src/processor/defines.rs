@@ -1,8 +1,8 @@
-use std::{collections::HashMap, rc::Rc, sync::Arc};
+use std::{collections::HashMap, sync::Arc};
 
 use hemtt_common::{
     position::Position,
-    reporting::{Output, Symbol, Token},
+    reporting::{Expansion, Output, Symbol, Token},
 };
 use peekmore::{PeekMore, PeekMoreIterator};
 
@@ -25,11 +25,14 @@ impl Processor {
     /// Expects the stream to be at the left parenthesis
     ///
     /// The stream is left after the closing parenthesis
+    ///
+    /// Macros used in the arguments are expanded, and pushed to `nested`
     pub(crate) fn call_read_args(
         &mut self,
         callsite: &Position,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
-    ) -> Result<Option<Vec<Vec<Rc<Token>>>>, Error> {
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+        nested: &mut Vec<Output>,
+    ) -> Result<Option<Vec<Vec<Arc<Token>>>>, Error> {
         if !stream
             .peek()
             .expect("peeked by caller")
@@ -56,13 +59,10 @@ impl Processor {
                 if self.defines.contains_key(word) {
                     let mut inner = Vec::new();
                     self.define_use(callsite, stream, &mut inner)?;
-                    arg.append(
-                        &mut inner
-                            .into_iter()
-                            .map(std::convert::Into::into)
-                            .collect::<Vec<Vec<Rc<Token>>>>()
-                            .concat(),
-                    );
+                    for output in inner {
+                        arg.extend(Vec::<Arc<Token>>::from(&output));
+                        nested.push(output);
+                    }
                     continue;
                 }
             } else if symbol.is_left_paren() {
@@ -95,8 +95,8 @@ impl Processor {
     ///
     /// The stream is left after the closing parenthesis
     pub(crate) fn define_read_args(
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
-    ) -> Result<Vec<Rc<Token>>, Error> {
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Result<Vec<Arc<Token>>, Error> {
         if !stream
             .next()
             .expect("peeked by caller")
@@ -108,7 +108,7 @@ impl Processor {
                 stream.peek().expect("peeked above").symbol()
             );
         }
-        let mut args: Vec<Rc<Token>> = Vec::new();
+        let mut args: Vec<Arc<Token>> = Vec::new();
         let mut comma_next = false;
         while let Some(token) = stream.peek() {
             let symbol = token.symbol();
@@ -153,8 +153,8 @@ impl Processor {
     /// The stream is left at the start of the next line
     pub(crate) fn define_read_body(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
-    ) -> Vec<Rc<Token>> {
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Vec<Arc<Token>> {
         self.skip_whitespace(stream, None);
         let mut body = Vec::new();
         for token in stream.by_ref() {
@@ -162,7 +162,7 @@ impl Processor {
             if symbol.is_newline() {
                 if body
                     .last()
-                    .map_or(false, |t: &Rc<Token>| t.symbol().is_escape())
+                    .map_or(false, |t: &Arc<Token>| t.symbol().is_escape())
                 {
                     // remove the backslash
                     body.pop();
@@ -185,7 +185,7 @@ impl Processor {
     pub(crate) fn define_use(
         &mut self,
         callsite: &Position,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: &mut Vec<Output>,
     ) -> Result<(), Error> {
         let ident = Self::current_word(stream)?;
@@ -194,9 +194,11 @@ impl Processor {
             buffer.push(Output::Direct(ident));
             return Ok(());
         };
+        self.used.insert(source.position().clone());
         match body {
             Definition::Function(function) => {
-                let Some(args) = self.call_read_args(callsite, stream)? else {
+                let mut nested = Vec::new();
+                let Some(args) = self.call_read_args(callsite, stream, &mut nested)? else {
                     #[allow(clippy::redundant_clone)] // behind hls feature flag
                     return Err(Error::Code(Box::new(FunctionAsValue {
                         token: Box::new(ident.as_ref().clone()),
@@ -211,6 +213,7 @@ impl Processor {
                         &self.defines.clone(),
                     ))));
                 }
+                let arguments = args.clone();
                 let mut arg_defines = HashMap::new();
                 for (arg, value) in function.args().iter().zip(args) {
                     arg_defines.insert(
@@ -226,7 +229,16 @@ impl Processor {
                     &mut function.stream(),
                     &mut layer,
                 )?;
-                buffer.push(Output::Macro(ident, layer));
+                #[allow(clippy::redundant_clone)] // behind hls feature flag
+                buffer.push(Output::Macro(
+                    ident,
+                    layer,
+                    Expansion {
+                        definition: source.clone(),
+                        arguments,
+                        nested,
+                    },
+                ));
                 self.defines.pop();
             }
             #[allow(clippy::needless_collect)] // causes recursion at runtime otherwise
@@ -239,7 +251,16 @@ impl Processor {
                     &mut body.into_iter().peekmore(),
                     &mut layer,
                 )?;
-                buffer.push(Output::Macro(ident, layer));
+                #[allow(clippy::redundant_clone)] // behind hls feature flag
+                buffer.push(Output::Macro(
+                    ident,
+                    layer,
+                    Expansion {
+                        definition: source.clone(),
+                        arguments: Vec::new(),
+                        nested: Vec::new(),
+                    },
+                ));
             }
             Definition::Void => return Ok(()),
             Definition::Unit => {
@@ -282,7 +303,11 @@ mod tests {
         let mut stream = tests::setup("(hello)");
         let mut processor = Processor::default();
         let args = processor
-            .call_read_args(&stream.peek().unwrap().position().clone(), &mut stream)
+            .call_read_args(
+                &stream.peek().unwrap().position().clone(),
+                &mut stream,
+                &mut Vec::new(),
+            )
             .unwrap()
             .unwrap();
         assert_eq!(args.len(), 1);
@@ -295,7 +320,11 @@ mod tests {
         let mut stream = tests::setup("(hello world)");
         let mut processor = Processor::default();
         let args = processor
-            .call_read_args(&stream.peek().unwrap().position().clone(), &mut stream)
+            .call_read_args(
+                &stream.peek().unwrap().position().clone(),
+                &mut stream,
+                &mut Vec::new(),
+            )
             .unwrap()
             .unwrap();
         assert_eq!(args.len(), 1);
@@ -310,7 +339,11 @@ mod tests {
         let mut stream = tests::setup("(hello,world)");
         let mut processor = Processor::default();
         let args = processor
-            .call_read_args(&stream.peek().unwrap().position().clone(), &mut stream)
+            .call_read_args(
+                &stream.peek().unwrap().position().clone(),
+                &mut stream,
+                &mut Vec::new(),
+            )
             .unwrap()
             .unwrap();
         assert_eq!(args.len(), 2);
@@ -325,7 +358,11 @@ mod tests {
         let mut stream = tests::setup("(hello, world)");
         let mut processor = Processor::default();
         let args = processor
-            .call_read_args(&stream.peek().unwrap().position().clone(), &mut stream)
+            .call_read_args(
+                &stream.peek().unwrap().position().clone(),
+                &mut stream,
+                &mut Vec::new(),
+            )
             .unwrap()
             .unwrap();
         assert_eq!(args.len(), 2);
@@ -341,7 +378,11 @@ mod tests {
         let mut stream = tests::setup("(hello world,world hello)");
         let mut processor = Processor::default();
         let args = processor
-            .call_read_args(&stream.peek().unwrap().position().clone(), &mut stream)
+            .call_read_args(
+                &stream.peek().unwrap().position().clone(),
+                &mut stream,
+                &mut Vec::new(),
+            )
             .unwrap()
             .unwrap();
         assert_eq!(args.len(), 2);
@@ -360,7 +401,11 @@ mod tests {
         let mut stream = tests::setup("(hello(world),world(hello))");
         let mut processor = Processor::default();
         let args = processor
-            .call_read_args(&stream.peek().unwrap().position().clone(), &mut stream)
+            .call_read_args(
+                &stream.peek().unwrap().position().clone(),
+                &mut stream,
+                &mut Vec::new(),
+            )
             .unwrap()
             .unwrap();
         assert_eq!(args.len(), 2);
@@ -381,7 +426,11 @@ mod tests {
         let mut stream = tests::setup("(set(1,2),set(3,4))");
         let mut processor = Processor::default();
         let args = processor
-            .call_read_args(&stream.peek().unwrap().position().clone(), &mut stream)
+            .call_read_args(
+                &stream.peek().unwrap().position().clone(),
+                &mut stream,
+                &mut Vec::new(),
+            )
             .unwrap()
             .unwrap();
         assert_eq!(args.len(), 4);
@@ -1,8 +1,8 @@
-use std::rc::Rc;
+use std::sync::Arc;
 
 use hemtt_common::{
     position::Position,
-    reporting::{Output, Symbol, Token},
+    reporting::{Output, Scope, Symbol, Token},
 };
 use peekmore::{PeekMore, PeekMoreIterator};
 
@@ -10,11 +10,10 @@ use crate::{
     codes::{
         pe12_include_not_found::IncludeNotFound, pe13_include_not_encased::IncludeNotEncased,
         pe14_include_unexpected_suffix::IncludeUnexpectedSuffix,
-        pe15_if_invalid_operator::IfInvalidOperator,
-        pe16_if_incompatible_types::IfIncompatibleType, pe2_unexpected_eof::UnexpectedEOF,
-        pe3_expected_ident::ExpectedIdent, pe4_unknown_directive::UnknownDirective,
-        pe6_change_builtin::ChangeBuiltin, pe7_if_unit_or_function::IfUnitOrFunction,
-        pe8_if_undefined::IfUndefined, pw1_redefine::RedefineMacro,
+        pe19_pragma_unknown::PragmaUnknown, pe20_pragma_invalid_scope::PragmaInvalidScope,
+        pe2_unexpected_eof::UnexpectedEOF, pe3_expected_ident::ExpectedIdent,
+        pe4_unknown_directive::UnknownDirective, pe6_change_builtin::ChangeBuiltin,
+        pw1_redefine::RedefineMacro,
     },
     defines::Defines,
     definition::{Definition, FunctionDefinition},
@@ -27,7 +26,7 @@ use super::Processor;
 impl Processor {
     pub(crate) fn directive(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: &mut Vec<Output>,
     ) -> Result<bool, Error> {
         if let Some(token) = stream.peek() {
@@ -46,7 +45,7 @@ impl Processor {
 
     pub(crate) fn directive_command(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: &mut Vec<Output>,
     ) -> Result<(), Error> {
         let command = stream.next().expect("was peeked in directive()");
@@ -64,12 +63,19 @@ impl Processor {
                 self.directive_undef(stream)?;
                 Ok(())
             }
-            ("if", true) => {
-                self.directive_if(command, stream)?;
-                Ok(())
+            ("if" | "ifdef" | "ifndef", true) => {
+                let depth = self.ifstates.depth();
+                let ret = if command_word == "if" {
+                    self.directive_if(command.clone(), stream)
+                } else {
+                    self.directive_ifdef(command.clone(), command_word == "ifdef", stream)
+                };
+                // keep the matching `#else` and `#endif` lined up when recovering from the error
+                if ret.is_err() && self.ifstates.depth() == depth {
+                    self.ifstates.push(IfState::PassingIf(command));
+                }
+                ret
             }
-            ("ifdef", true) => self.directive_ifdef(command, true, stream),
-            ("ifndef", true) => self.directive_ifdef(command, false, stream),
             ("if" | "ifdef" | "ifndef", false) => {
                 self.ifstates.push(IfState::PassingChild(command));
                 self.skip_to_after_newline(stream, None);
@@ -85,8 +91,8 @@ impl Processor {
                 Self::expect_nothing_to_newline(stream)?;
                 Ok(())
             }
-            ("pragma", _) | (_, false) => {
-                // TODO: hemtt pragma
+            ("pragma", true) => self.directive_pragma(&command, stream),
+            (_, false) => {
                 self.skip_to_after_newline(stream, None);
                 Ok(())
             }
@@ -98,11 +104,64 @@ impl Processor {
 
     pub(crate) fn directive_include(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: &mut Vec<Output>,
     ) -> Result<(), Error> {
         self.skip_whitespace(stream, None);
-        let open = stream.next().expect("was peeked in directive()");
+        let target = Self::include_path(stream)?;
+
+        if let Err(Error::Code(code)) = Self::expect_nothing_to_newline(stream) {
+            if let Some(token) = code.token() {
+                return Err(Error::Code(Box::new(IncludeUnexpectedSuffix {
+                    token: Box::new(token.clone()),
+                })));
+            }
+            return Err(Error::Code(code));
+        }
+
+        let target_path = target.iter().map(|t| t.to_string()).collect::<String>();
+        let Some(path) = self.locate(&target_path)? else {
+            return Err(Error::Code(Box::new(IncludeNotFound::new(target))));
+        };
+        let current = self
+            .files
+            .last()
+            .expect("root file should always be present");
+        let tokens = match &self.include_cache {
+            Some(cache) => cache.parse(&path)?,
+            None => crate::parse::parse(&path)?,
+        };
+        self.pragma.include(current, &path);
+        self.includes.push((current.clone(), path.clone()));
+        self.files.push(path);
+        let macros = self.macros.len();
+        let content = self.content_count;
+        let mut stream = tokens.into_iter().peekmore();
+        let ret = self.file(&mut stream, buffer);
+        self.files.pop();
+        self.include_usage.push((
+            Arc::new(Token::new(
+                Symbol::Word(target.iter().map(|t| t.to_string()).collect()),
+                Position::new(
+                    *target.first().expect("located above").position().start(),
+                    *target.last().expect("located above").position().end(),
+                    target[0].position().path().clone(),
+                ),
+            )),
+            macros..self.macros.len(),
+            self.content_count > content,
+        ));
+        ret
+    }
+
+    /// Read an encased include path, `"path"` or `<path>`
+    /// The stream is left after the closing enclosure
+    pub(crate) fn include_path(
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Result<Vec<Arc<Token>>, Error> {
+        let open = stream
+            .next()
+            .expect("stream should always end with an eoi token");
         if !open.symbol().is_include_enclosure() {
             return Err(Error::Code(Box::new(IncludeNotEncased {
                 encased_in: if open.symbol().is_word() {
@@ -136,36 +195,54 @@ impl Processor {
             }
             path.push(token);
         }
+        Ok(path)
+    }
 
-        if let Err(Error::Code(code)) = Self::expect_nothing_to_newline(stream) {
-            if let Some(token) = code.token() {
-                return Err(Error::Code(Box::new(IncludeUnexpectedSuffix {
-                    token: Box::new(token.clone()),
-                })));
-            }
-            return Err(Error::Code(code));
+    /// `#pragma hemtt suppress <code> [line|file|include]`
+    ///
+    /// Pragmas that are not for HEMTT are ignored
+    pub(crate) fn directive_pragma(
+        &mut self,
+        command: &Token,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Result<(), Error> {
+        self.skip_whitespace(stream, None);
+        let hemtt = stream
+            .peek()
+            .is_some_and(|t| t.symbol().to_string() == "hemtt");
+        if !hemtt {
+            self.skip_to_after_newline(stream, None);
+            return Ok(());
         }
-
-        let current = self
-            .files
-            .last()
-            .expect("root file should always be present");
-        let Ok(Some(path)) =
-            current.locate(&path.iter().map(|t| t.to_string()).collect::<String>())
-        else {
-            return Err(Error::Code(Box::new(IncludeNotFound::new(path))));
+        stream.next();
+        let pragma_command = self.next_word(stream, None)?;
+        if pragma_command.symbol().to_string() != "suppress" {
+            return Err(Error::Code(Box::new(PragmaUnknown {
+                token: Box::new(pragma_command.as_ref().clone()),
+            })));
+        }
+        let name = self.next_word(stream, None)?.symbol().to_string();
+        self.skip_whitespace(stream, None);
+        let scope = if stream.peek().is_some_and(|t| t.symbol().is_word()) {
+            let scope = stream.next().expect("was peeked");
+            scope.symbol().to_string().parse().map_err(|()| {
+                Error::Code(Box::new(PragmaInvalidScope {
+                    token: Box::new(scope.as_ref().clone()),
+                }))
+            })?
+        } else {
+            Scope::Line
         };
-        let tokens = crate::parse::parse(&path)?;
-        self.files.push(path);
-        let mut stream = tokens.into_iter().peekmore();
-        let ret = self.file(&mut stream, buffer);
-        self.files.pop();
-        ret
+        Self::expect_nothing_to_newline(stream)?;
+        let path = command.position().path();
+        let line = command.position().start().1 .0;
+        self.pragma.suppress(&name, scope, path, line);
+        Ok(())
     }
 
     pub(crate) fn directive_define(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
     ) -> Result<(), Error> {
         let ident = self.next_word(stream, None)?;
         if !ident.symbol().is_word() {
@@ -187,7 +264,11 @@ impl Processor {
         if let Some((original, _)) = self.defines.remove(&ident_string) {
             self.warnings.push(Box::new(RedefineMacro {
                 token: Box::new(ident.as_ref().clone()),
-                original: Box::new(original.as_ref().clone()),
+                original: (!self
+                    .external
+                    .iter()
+                    .any(|token| Arc::ptr_eq(token, &original)))
+                .then(|| Box::new(original.as_ref().clone())),
             }));
         }
         let definition = match next.symbol() {
@@ -210,13 +291,17 @@ impl Processor {
         };
         #[cfg(feature = "lsp")]
         self.usage.insert(ident.position().clone(), Vec::new());
+        // an include guard is only tested by `#ifndef`, it is not reported as unused
+        if definition != Definition::Unit || !self.guards.contains(&ident_string) {
+            self.macros.push(ident.clone());
+        }
         self.defines.insert(&ident_string, (ident, definition));
         Ok(())
     }
 
     pub(crate) fn directive_undef(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
     ) -> Result<(), Error> {
         let ident = self.next_word(stream, None)?;
         if !ident.symbol().is_word() {
@@ -229,116 +314,11 @@ impl Processor {
         Self::expect_nothing_to_newline(stream)
     }
 
-    #[allow(clippy::too_many_lines)]
-    pub(crate) fn directive_if(
-        &mut self,
-        command: Rc<Token>,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
-    ) -> Result<(), Error> {
-        fn value(defines: &mut Defines, token: Rc<Token>) -> Result<(Vec<Rc<Token>>, bool), Error> {
-            if let Some((_, definition)) = defines.get_with_gen(&token, Some(token.position())) {
-                if let Definition::Value(tokens) = definition {
-                    return Ok((tokens, true));
-                }
-                return Err(Error::Code(Box::new(IfUnitOrFunction::new(
-                    Box::new(token.as_ref().clone()),
-                    &defines.clone(),
-                ))));
-            }
-            Ok((vec![token], false))
-        }
-        let left = self.next_value(stream, None)?;
-        let (left, left_defined) = value(&mut self.defines, left)?;
-        self.skip_whitespace(stream, None);
-        let mut operators = Vec::with_capacity(2);
-        let (right, right_defined) = if stream.peek().map(|t| t.symbol()) == Some(&Symbol::Newline)
-        {
-            let pos = stream.peek().unwrap().position().clone();
-            if !left_defined {
-                return Err(Error::Code(Box::new(IfUndefined::new(
-                    Box::new(left[0].as_ref().clone()),
-                    &self.defines,
-                ))));
-            }
-            let equals = Rc::new(Token::new(Symbol::Equals, pos.clone()));
-            operators = vec![equals.clone(), equals];
-            (vec![Rc::new(Token::new(Symbol::Digit(1), pos))], false)
-        } else {
-            loop {
-                let Some(token) = stream.peek() else {
-                    return Err(Error::Code(Box::new(UnexpectedEOF {
-                        token: Box::new(
-                            left.last()
-                                .expect("left should exists at this point")
-                                .as_ref()
-                                .clone(),
-                        ),
-                    })));
-                };
-                if matches!(token.symbol(), Symbol::Whitespace(_)) {
-                    stream.next();
-                    break;
-                }
-                operators.push(token.clone());
-                stream.next();
-            }
-            let Some(right) = stream.next() else {
-                return Err(Error::Code(Box::new(UnexpectedEOF {
-                    token: Box::new(
-                        left.last()
-                            .expect("left should exists at this point")
-                            .as_ref()
-                            .clone(),
-                    ),
-                })));
-            };
-            value(&mut self.defines, right)?
-        };
-        let operator = operators.iter().map(|t| t.to_string()).collect::<String>();
-        let left_string = left.iter().map(|t| t.to_string()).collect::<String>();
-        let right_string = right.iter().map(|t| t.to_string()).collect::<String>();
-        let read = match operator.as_str() {
-            "==" => left_string == right_string,
-            "!=" => left_string != right_string,
-            ">" | ">=" | "<" | "<=" => {
-                let Ok(left_f64) = left_string.parse::<f64>() else {
-                    return Err(Error::Code(Box::new(IfIncompatibleType::new(
-                        (left, left_defined),
-                        operators,
-                        (right, right_defined),
-                    ))));
-                };
-                let Ok(right_f64) = right_string.parse::<f64>() else {
-                    return Err(Error::Code(Box::new(IfIncompatibleType::new(
-                        (left, left_defined),
-                        operators,
-                        (right, right_defined),
-                    ))));
-                };
-                match operator.as_str() {
-                    ">" => left_f64 > right_f64,
-                    ">=" => left_f64 >= right_f64,
-                    "<" => left_f64 < right_f64,
-                    "<=" => left_f64 <= right_f64,
-                    _ => unreachable!(),
-                }
-            }
-            _ => {
-                return Err(Error::Code(Box::new(IfInvalidOperator {
-                    tokens: operators.iter().map(|t| t.as_ref().clone()).collect(),
-                })))
-            }
-        };
-        self.ifstates.push_if(command, read);
-        Self::expect_nothing_to_newline(stream)?;
-        Ok(())
-    }
-
     pub(crate) fn directive_ifdef(
         &mut self,
-        command: Rc<Token>,
+        command: Arc<Token>,
         outcome: bool,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
     ) -> Result<(), Error> {
         let ident = self.next_word(stream, None)?;
         if !ident.symbol().is_word() {
@@ -347,8 +327,12 @@ impl Processor {
             })));
         }
         let ident_string = ident.symbol().to_string();
-        self.ifstates
-            .push_if(command, self.defines.contains_key(&ident_string) == outcome);
+        self.use_macro(&ident_string);
+        let defined = self.defines.contains_key(&ident_string);
+        if !outcome && !defined {
+            self.guards.insert(ident_string);
+        }
+        self.ifstates.push_if(command, defined == outcome);
         Self::expect_nothing_to_newline(stream)
     }
 }
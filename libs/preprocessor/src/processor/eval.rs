@@ -0,0 +1,229 @@
+use std::{collections::HashMap, sync::Arc};
+
+use hemtt_common::{
+    math::eval_with_variables,
+    position::Position,
+    reporting::{Output, Symbol, Token},
+};
+use peekmore::{PeekMore, PeekMoreIterator};
+
+use crate::{
+    codes::{pe21_eval_invalid::EvalInvalid, pe2_unexpected_eof::UnexpectedEOF},
+    Error,
+};
+
+use super::Processor;
+
+#[derive(Debug, Clone, PartialEq)]
+/// The result of an `__EVAL`
+pub enum Evaluated {
+    /// A number, the game stores these as floats
+    Number(f64),
+    /// A string
+    Str(String),
+}
+
+impl Evaluated {
+    /// The value as it would be written in a config
+    pub fn to_config(&self) -> String {
+        match self {
+            Self::Number(value) => format!("{value:?}"),
+            Self::Str(value) => format!("\"{}\"", value.replace('"', "\"\"")),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+/// Variables assigned by `__EXEC`, shared by every `__EVAL` after it
+pub struct Exec {
+    numbers: HashMap<String, f64>,
+    strings: HashMap<String, String>,
+}
+
+impl Exec {
+    /// Evaluate an expression of numbers, strings and variables
+    ///
+    /// Returns `None` if the expression is not supported
+    pub fn evaluate(&self, expression: &str) -> Option<Evaluated> {
+        let terms = split_outside_quotes(expression, '+');
+        let is_string = terms.iter().any(|term| {
+            unquote(term).is_some() || self.strings.contains_key(&term.trim().to_lowercase())
+        });
+        if !is_string {
+            return eval_with_variables(expression, &self.numbers)
+                .filter(|value| value.is_finite())
+                .map(Evaluated::Number);
+        }
+        let mut value = String::new();
+        for term in terms {
+            if let Some(literal) = unquote(term) {
+                value.push_str(&literal);
+            } else if let Some(string) = self.strings.get(&term.trim().to_lowercase()) {
+                value.push_str(string);
+            } else {
+                return None;
+            }
+        }
+        Some(Evaluated::Str(value))
+    }
+
+    /// Run the assignments of an `__EXEC`, separated by `;`
+    ///
+    /// Returns `None` if a statement is not a supported assignment
+    pub fn execute(&mut self, statements: &str) -> Option<()> {
+        for statement in split_outside_quotes(statements, ';') {
+            if statement.trim().is_empty() {
+                continue;
+            }
+            let (name, expression) = statement.split_once('=')?;
+            let name = name.trim().to_lowercase();
+            if name.is_empty()
+                || name.starts_with(|c: char| c.is_ascii_digit())
+                || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            {
+                return None;
+            }
+            match self.evaluate(expression)? {
+                Evaluated::Number(value) => {
+                    self.strings.remove(&name);
+                    self.numbers.insert(name, value);
+                }
+                Evaluated::Str(value) => {
+                    self.numbers.remove(&name);
+                    self.strings.insert(name, value);
+                }
+            }
+        }
+        Some(())
+    }
+}
+
+/// Split on a character that is not inside a string
+fn split_outside_quotes(source: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (i, c) in source.char_indices() {
+        match (c, quote) {
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(q)) if c == q => quote = None,
+            (c, None) if c == separator => {
+                parts.push(&source[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&source[start..]);
+    parts
+}
+
+/// The contents of a string literal, quotes inside are escaped by doubling them
+fn unquote(term: &str) -> Option<String> {
+    let term = term.trim();
+    let quote = term.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    if term.len() < 2 || !term.ends_with(quote) {
+        return None;
+    }
+    let inner = &term[1..term.len() - 1];
+    let doubled = format!("{quote}{quote}");
+    if inner.replace(&doubled, "").contains(quote) {
+        return None;
+    }
+    Some(inner.replace(&doubled, &quote.to_string()))
+}
+
+impl Processor {
+    /// `__EVAL(expression)` or `__EXEC(statements)`
+    ///
+    /// The stream is left after the closing parenthesis
+    pub(crate) fn eval_builtin(
+        &mut self,
+        callsite: Option<&Position>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+        buffer: &mut Vec<Output>,
+    ) -> Result<(), Error> {
+        let command = stream.next().expect("peeked in walk");
+        stream.next();
+        let mut depth = 0;
+        let mut tokens = Vec::new();
+        loop {
+            let Some(token) = stream.next().filter(|t| !t.symbol().is_eoi()) else {
+                return Err(Error::Code(Box::new(UnexpectedEOF {
+                    token: Box::new(command.as_ref().clone()),
+                })));
+            };
+            match token.symbol() {
+                Symbol::LeftParenthesis => depth += 1,
+                Symbol::RightParenthesis if depth == 0 => break,
+                Symbol::RightParenthesis => depth -= 1,
+                _ => {}
+            }
+            tokens.push(token);
+        }
+        let command_word = command.symbol().to_string();
+        let mut expanded = Vec::new();
+        self.walk(
+            Some(callsite.unwrap_or_else(|| command.position())),
+            Some(&command_word),
+            &mut tokens.into_iter().peekmore(),
+            &mut expanded,
+        )?;
+        let expression = expanded.iter().map(ToString::to_string).collect::<String>();
+        let invalid = || {
+            Error::Code(Box::new(EvalInvalid {
+                token: Box::new(command.as_ref().clone()),
+                expression: expression.clone(),
+            }))
+        };
+        if command_word == "__EXEC" {
+            self.exec.execute(&expression).ok_or_else(invalid)?;
+        } else {
+            let value = self.exec.evaluate(&expression).ok_or_else(invalid)?;
+            self.output(
+                Arc::new(Token::new(
+                    Symbol::Word(value.to_config()),
+                    command.position().clone(),
+                )),
+                buffer,
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Evaluated, Exec};
+
+    #[test]
+    fn evaluate() {
+        let mut exec = Exec::default();
+        assert_eq!(exec.evaluate("1 / 4"), Some(Evaluated::Number(0.25)));
+        assert_eq!(
+            exec.evaluate("\"a\" + 'b'"),
+            Some(Evaluated::Str("ab".to_string()))
+        );
+        assert_eq!(exec.evaluate("1 / 0"), None);
+        assert_eq!(
+            exec.execute("_x = 2; _name = \"gun\"; _y = _x * 3"),
+            Some(())
+        );
+        assert_eq!(exec.evaluate("_Y + 1"), Some(Evaluated::Number(7.0)));
+        assert_eq!(
+            exec.evaluate("_name + \"_F\""),
+            Some(Evaluated::Str("gun_F".to_string()))
+        );
+        assert_eq!(exec.evaluate("_missing"), None);
+        assert_eq!(exec.execute("hint \"no\""), None);
+    }
+
+    #[test]
+    fn to_config() {
+        assert_eq!(Evaluated::Number(2.0).to_config(), "2.0");
+        assert_eq!(
+            Evaluated::Str("say \"hi\"".to_string()).to_config(),
+            "\"say \"\"hi\"\"\""
+        );
+    }
+}
@@ -1,10 +1,10 @@
 #[cfg(feature = "lsp")]
 use std::collections::HashMap;
-use std::rc::Rc;
+use std::{collections::HashSet, ops::Range, sync::Arc};
 
-use hemtt_common::position::Position;
-use hemtt_common::reporting::{Code, Output, Processed, Symbol, Token};
-use hemtt_common::workspace::WorkspacePath;
+use hemtt_common::position::{LineCol, Position};
+use hemtt_common::reporting::{Code, MacroUsage, Output, Pragma, Processed, Symbol, Token};
+use hemtt_common::workspace::{Workspace, WorkspacePath};
 use peekmore::{PeekMore, PeekMoreIterator};
 
 use crate::codes::pe18_eoi_ifstate::EoiIfState;
@@ -12,13 +12,23 @@ use crate::codes::pe2_unexpected_eof::UnexpectedEOF;
 use crate::codes::pe3_expected_ident::ExpectedIdent;
 use crate::codes::pw2_invalid_config_case::InvalidConfigCase;
 use crate::defines::Defines;
+use crate::definition::Definition;
 use crate::ifstate::IfStates;
 use crate::Error;
+use crate::IncludeCache;
+use crate::IncludeResolver;
 
+use self::eval::Exec;
+
+mod condition;
 mod defines;
 mod directives;
+mod eval;
 mod whitespace;
 
+/// Errors that stop preprocessing, the rest of the file can not be checked without them
+const FATAL: [&str; 1] = ["PE12"];
+
 #[derive(Default)]
 /// Arma 3 Preprocessor
 pub struct Processor {
@@ -29,6 +39,9 @@ pub struct Processor {
 
     pub(crate) token_count: usize,
 
+    /// Number of output tokens that are not whitespace
+    content_count: usize,
+
     #[cfg(feature = "lsp")]
     /// Map of token usage to definition
     /// (token, definition)
@@ -41,6 +54,39 @@ pub struct Processor {
 
     /// Warnings
     pub(crate) warnings: Vec<Box<dyn Code>>,
+
+    /// Errors that were recovered from
+    errors: Vec<Box<dyn Code>>,
+
+    /// Suppressions from `#pragma hemtt`
+    pub(crate) pragma: Pragma,
+
+    /// Variables assigned by `__EXEC`
+    pub(crate) exec: Exec,
+
+    /// Tokenized include files shared with other processors
+    pub(crate) include_cache: Option<IncludeCache>,
+
+    /// Finds included files for source that is not in a workspace
+    pub(crate) resolver: Option<Box<dyn IncludeResolver>>,
+
+    /// Files included by `#include`, as (file, included file)
+    pub(crate) includes: Vec<(WorkspacePath, WorkspacePath)>,
+
+    /// Name of every macro defined by `#define`
+    pub(crate) macros: Vec<Arc<Token>>,
+
+    /// Name of every macro defined by the workspace, which has no definition in a file
+    pub(crate) external: Vec<Arc<Token>>,
+
+    /// Macros tested by `#ifndef` before they were defined, likely include guards
+    pub(crate) guards: HashSet<String>,
+
+    /// Positions of the definitions of macros that were used
+    pub(crate) used: HashSet<Position>,
+
+    /// Path of every `#include`, the range of `macros` it defined, and if it output anything
+    pub(crate) include_usage: Vec<(Arc<Token>, Range<usize>, bool)>,
 }
 
 impl Processor {
@@ -55,9 +101,57 @@ impl Processor {
     /// # Errors
     /// See [`Error`]
     pub fn run(path: &WorkspacePath) -> Result<Processed, Error> {
-        let mut processor = Self::default();
+        Self::run_inner(path, Self::default())
+    }
+
+    /// Preprocess a file, sharing tokenized include files with other files using the same cache
+    ///
+    /// # Errors
+    /// See [`Error`]
+    pub fn run_cached(path: &WorkspacePath, cache: &IncludeCache) -> Result<Processed, Error> {
+        Self::run_inner(
+            path,
+            Self {
+                include_cache: Some(cache.clone()),
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Preprocess source text, without a workspace
+    ///
+    /// `name` is the path of the source, used in reports and to find relative includes.
+    /// `defines` are added before the source as `(name, value)`, an empty value defines a flag.
+    /// They are given as text rather than [`Defines`], so they are tokenized like the defines
+    /// of a project, and reported as defined by the project when a file redefines them.
+    /// Every `#include` is found with the `resolver`.
+    ///
+    /// # Errors
+    /// See [`Error`]
+    pub fn run_str(
+        name: &str,
+        source: &str,
+        defines: Vec<(String, String)>,
+        resolver: impl IncludeResolver + 'static,
+    ) -> Result<Processed, Error> {
+        let path = Workspace::builder()
+            .memory()
+            .defines(defines)
+            .finish(None)?
+            .join(name.replace('\\', "/").trim_start_matches('/'))?;
+        crate::resolver::write(&path, source)?;
+        Self::run_inner(
+            &path,
+            Self {
+                resolver: Some(Box::new(resolver)),
+                ..Default::default()
+            },
+        )
+    }
 
+    fn run_inner(path: &WorkspacePath, mut processor: Self) -> Result<Processed, Error> {
         processor.files.push(path.clone());
+        processor.workspace_defines(path)?;
 
         let tokens = crate::parse::parse(path)?;
         let mut buffer = Vec::with_capacity(tokens.len());
@@ -65,10 +159,18 @@ impl Processor {
 
         processor.file(&mut stream, &mut buffer)?;
 
-        if let Some(state) = processor.ifstates.pop() {
-            return Err(Error::Code(Box::new(EoiIfState {
+        // an unterminated `#if` after another error is likely caused by it
+        if let (Some(state), true) = (processor.ifstates.pop(), processor.errors.is_empty()) {
+            processor.errors.push(Box::new(EoiIfState {
                 token: Box::new(state.token().as_ref().clone()),
-            })));
+            }));
+        }
+
+        if processor.errors.len() > 1 {
+            return Err(Error::Codes(processor.errors));
+        }
+        if let Some(error) = processor.errors.pop() {
+            return Err(Error::Code(error));
         }
 
         if path.filename() == "Config.cpp" {
@@ -77,6 +179,7 @@ impl Processor {
                 .push(Box::new(InvalidConfigCase { path: path.clone() }));
         }
 
+        let macro_usage = processor.macro_usage();
         Processed::new(
             buffer,
             #[cfg(feature = "lsp")]
@@ -84,27 +187,131 @@ impl Processor {
             #[cfg(feature = "lsp")]
             processor.declarations,
             processor.warnings,
+            processor.pragma,
+            processor.includes,
+            macro_usage,
         )
         .map_err(Into::into)
     }
 
+    /// Find an included file from the current file, with the resolver if there is one
+    pub(crate) fn locate(&self, target: &str) -> Result<Option<WorkspacePath>, Error> {
+        let current = self
+            .files
+            .last()
+            .expect("root file should always be present");
+        self.resolver.as_ref().map_or_else(
+            || Ok(current.locate(target).ok().flatten()),
+            |resolver| crate::resolver::locate(resolver.as_ref(), current, target),
+        )
+    }
+
+    /// Mark the current definition of a macro as used
+    pub(crate) fn use_macro(&mut self, name: &str) {
+        if let Some((definition, _)) = self.defines.get_readonly(name) {
+            self.used.insert(definition.position().clone());
+        }
+    }
+
+    /// Which macros and includes were used
+    fn macro_usage(&self) -> MacroUsage {
+        let used = |token: &Arc<Token>| self.used.contains(token.position());
+        MacroUsage {
+            macros: self
+                .macros
+                .iter()
+                .map(|token| (token.clone(), used(token)))
+                .collect(),
+            includes: self
+                .include_usage
+                .iter()
+                .map(|(token, defined, output)| {
+                    (
+                        token.clone(),
+                        *output || self.macros[defined.clone()].iter().any(used),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// Add the defines of the workspace, set by the project or on the command line
+    fn workspace_defines(&mut self, path: &WorkspacePath) -> Result<(), Error> {
+        for (name, value) in path.workspace().defines() {
+            let definition = if value.is_empty() {
+                Definition::Unit
+            } else {
+                let mut tokens = crate::parse::parse_str(value, path)?;
+                tokens.pop(); // eoi
+                Definition::Value(tokens)
+            };
+            let start = LineCol(0, (1, 0));
+            let token = Arc::new(Token::new(
+                Symbol::Word(name.clone()),
+                Position::new(start, start, path.clone()),
+            ));
+            self.external.push(token.clone());
+            self.defines.insert(name, (token, definition));
+        }
+        Ok(())
+    }
+
     fn file(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: &mut Vec<Output>,
     ) -> Result<(), Error> {
         loop {
-            let first = stream.peek();
-            if first.is_none() || first.expect("just checked").symbol().is_eoi() {
+            let Some(first) = stream.peek().cloned() else {
                 return Ok(());
+            };
+            if first.symbol().is_eoi() {
+                return Ok(());
+            }
+            if let Err(error) = self.line(stream, buffer) {
+                self.recover(error, &first, stream)?;
             }
-            self.line(stream, buffer)?;
         }
     }
 
+    /// Record an error and skip to the start of the line after it, so the rest of the file is still checked
+    ///
+    /// # Errors
+    /// The error, if it can not be recovered from
+    fn recover(
+        &mut self,
+        error: Error,
+        first: &Token,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Result<(), Error> {
+        let Error::Code(code) = error else {
+            return Err(error);
+        };
+        if FATAL.contains(&code.ident()) {
+            return Err(Error::Code(code));
+        }
+        let mut line = first.position().start().line();
+        if let Some(token) = code.token() {
+            if token.position().path() == first.position().path() {
+                line = line.max(token.position().start().line());
+            }
+        }
+        while let Some(token) = stream.peek() {
+            if token.symbol().is_eoi() || token.position().start().line() > line {
+                break;
+            }
+            stream.next();
+        }
+        while !self.defines.stack().is_empty() {
+            self.defines.pop();
+        }
+        self.errors.push(code);
+        Ok(())
+    }
+
     fn line(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: &mut Vec<Output>,
     ) -> Result<(), Error> {
         self.skip_whitespace(stream, Some(buffer));
@@ -123,13 +330,21 @@ impl Processor {
         &mut self,
         callsite: Option<&Position>,
         in_macro: Option<&str>,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: &mut Vec<Output>,
     ) -> Result<(), Error> {
         let mut in_quotes = false;
         let mut quote = None;
-        while let Some(token) = stream.peek() {
+        while let Some(token) = stream.peek().cloned() {
             match (token.symbol(), in_quotes) {
+                (Symbol::Word(w), false)
+                    if (w == "__EVAL" || w == "__EXEC")
+                        && stream
+                            .peek_nth(1)
+                            .map_or(false, |t| t.symbol() == &Symbol::LeftParenthesis) =>
+                {
+                    self.eval_builtin(callsite, stream, buffer)?;
+                }
                 (Symbol::Word(w), false) => {
                     if Some(w.as_str()) != in_macro && self.defines.contains_key(w) {
                         let token = token.clone();
@@ -151,7 +366,7 @@ impl Processor {
                             || token.position().start().0 < callsite.unwrap().start().0)
                     {
                         self.output(
-                            Rc::new(Token::new(Symbol::DoubleQuote, token.position().clone())),
+                            Arc::new(Token::new(Symbol::DoubleQuote, token.position().clone())),
                             buffer,
                         );
                         quote = Some(token.position().clone());
@@ -177,7 +392,7 @@ impl Processor {
                 }
             }
             if let Some(quote) = quote {
-                self.output(Rc::new(Token::new(Symbol::DoubleQuote, quote)), buffer);
+                self.output(Arc::new(Token::new(Symbol::DoubleQuote, quote)), buffer);
             }
             quote = None;
         }
@@ -190,8 +405,8 @@ impl Processor {
     /// - [`UnexpectedEOF`]: If the stream is at the end of the file
     /// - [`ExpectedIdent`]: If the stream is not at a word
     fn current_word(
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
-    ) -> Result<Rc<Token>, Error> {
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
+    ) -> Result<Arc<Token>, Error> {
         if let Some(token) = stream.peek() {
             if token.symbol().is_word() {
                 return Ok(stream.next().expect("just checked"));
@@ -214,34 +429,14 @@ impl Processor {
     /// - [`ExpectedIdent`]: If the stream is not at a word
     fn next_word(
         &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
+        stream: &mut PeekMoreIterator<impl Iterator<Item = Arc<Token>>>,
         buffer: Option<&mut Vec<Output>>,
-    ) -> Result<Rc<Token>, Error> {
+    ) -> Result<Arc<Token>, Error> {
         self.skip_whitespace(stream, buffer);
         Self::current_word(stream)
     }
 
-    /// Skips whitespace, returning the next value and consuming it from the stream
-    ///
-    /// # Errors
-    /// - [`UnexpectedEOF`]: If the stream is at the end of the file
-    fn next_value(
-        &mut self,
-        stream: &mut PeekMoreIterator<impl Iterator<Item = Rc<Token>>>,
-        buffer: Option<&mut Vec<Output>>,
-    ) -> Result<Rc<Token>, Error> {
-        self.skip_whitespace(stream, buffer);
-        if let Some(token) = stream.peek() {
-            if token.symbol().is_eoi() {
-                return Err(Error::Code(Box::new(UnexpectedEOF {
-                    token: Box::new(token.as_ref().clone()),
-                })));
-            }
-        }
-        Ok(stream.next().expect("just checked"))
-    }
-
-    fn output(&mut self, token: Rc<Token>, buffer: &mut Vec<Output>) {
+    fn output(&mut self, token: Arc<Token>, buffer: &mut Vec<Output>) {
         if self.ifstates.reading() && !token.symbol().is_comment() {
             if token.symbol().is_newline()
                 && buffer
@@ -252,6 +447,9 @@ impl Processor {
                 return;
             }
             self.token_count += 1;
+            if !token.symbol().is_whitespace() && !token.symbol().is_newline() {
+                self.content_count += 1;
+            }
             buffer.push(Output::Direct(token));
         }
     }
@@ -259,12 +457,12 @@ impl Processor {
 
 #[cfg(test)]
 pub mod tests {
-    use std::rc::Rc;
+    use std::sync::Arc;
 
     use hemtt_common::reporting::Token;
     use peekmore::{PeekMore, PeekMoreIterator};
 
-    pub fn setup(content: &str) -> PeekMoreIterator<impl Iterator<Item = Rc<Token>>> {
+    pub fn setup(content: &str) -> PeekMoreIterator<impl Iterator<Item = Arc<Token>>> {
         let workspace = hemtt_common::workspace::Workspace::builder()
             .memory()
             .finish(None)
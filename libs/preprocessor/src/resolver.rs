@@ -0,0 +1,63 @@
+use std::{collections::HashMap, hash::BuildHasher, io::Write};
+
+use hemtt_common::workspace::WorkspacePath;
+
+use crate::Error;
+
+/// Finds the files included by source preprocessed with [`crate::Processor::run_str`]
+pub trait IncludeResolver: Send + Sync {
+    /// Find an included file
+    ///
+    /// `from` is the path of the including file, `target` is the path as written in the `#include`
+    ///
+    /// Returns the path and content of the included file, or `None` if it could not be found
+    fn resolve(&self, from: &str, target: &str) -> Option<(String, String)>;
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+/// Finds no files, every `#include` is an error
+pub struct NoIncludes;
+
+impl IncludeResolver for NoIncludes {
+    fn resolve(&self, _from: &str, _target: &str) -> Option<(String, String)> {
+        None
+    }
+}
+
+/// Finds files by their path, relative to the including file or from the root
+impl<S: BuildHasher + Send + Sync> IncludeResolver for HashMap<String, String, S> {
+    fn resolve(&self, from: &str, target: &str) -> Option<(String, String)> {
+        let target = target.replace('\\', "/");
+        let relative = from
+            .rsplit_once('/')
+            .map_or_else(|| target.clone(), |(dir, _)| format!("{dir}/{target}"));
+        [relative, target.trim_start_matches('/').to_string()]
+            .into_iter()
+            .find_map(|path| self.get(&path).map(|content| (path, content.clone())))
+    }
+}
+
+/// Locate an included file with a resolver, adding it to the in-memory workspace
+pub fn locate(
+    resolver: &dyn IncludeResolver,
+    from: &WorkspacePath,
+    target: &str,
+) -> Result<Option<WorkspacePath>, Error> {
+    let Some((path, content)) = resolver.resolve(from.as_str().trim_start_matches('/'), target)
+    else {
+        return Ok(None);
+    };
+    let file = from.root().join(path.trim_start_matches('/'))?;
+    write(&file, &content)?;
+    Ok(Some(file))
+}
+
+/// Write a file to the in-memory workspace, creating its folders
+pub fn write(file: &WorkspacePath, content: &str) -> Result<(), Error> {
+    file.parent()
+        .vfs()
+        .create_dir_all()
+        .map_err(hemtt_common::workspace::Error::from)?;
+    file.create_file()?.write_all(content.as_bytes())?;
+    Ok(())
+}
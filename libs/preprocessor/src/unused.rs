@@ -0,0 +1,97 @@
+use std::{collections::HashMap, sync::Arc};
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Processed, Token},
+};
+
+use crate::codes::{pw3_unused_macro::UnusedMacro, pw4_unused_include::UnusedInclude};
+
+#[derive(Default)]
+/// Tokens in the order they were first seen, with if they were used and if the warning is suppressed
+struct Entries {
+    order: Vec<Arc<Token>>,
+    state: HashMap<Position, (bool, bool)>,
+}
+
+impl Entries {
+    fn add(&mut self, entries: &[(Arc<Token>, bool)], suppressed: impl Fn(&Arc<Token>) -> bool) {
+        for (token, used) in entries {
+            if !self.state.contains_key(token.position()) {
+                self.order.push(token.clone());
+            }
+            let state = self
+                .state
+                .entry(token.position().clone())
+                .or_insert((false, false));
+            state.0 |= *used;
+            state.1 |= suppressed(token);
+        }
+    }
+
+    fn unused(&self) -> impl Iterator<Item = &Arc<Token>> {
+        self.order.iter().filter(|token| {
+            self.state
+                .get(token.position())
+                .is_some_and(|(used, suppressed)| !used && !suppressed)
+        })
+    }
+}
+
+#[derive(Default)]
+/// Macros and includes that are never used, in any of the files they were preprocessed in
+///
+/// A macro defined in a shared header is only unused if no file including the header uses it
+pub struct Unused {
+    macros: Entries,
+    includes: Entries,
+}
+
+impl Unused {
+    #[must_use]
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the macros and includes of a preprocessed file
+    pub fn add(&mut self, processed: &Processed) {
+        let usage = processed.macro_usage();
+        self.macros.add(&usage.macros, |token| {
+            processed.is_suppressed(&UnusedMacro {
+                token: token.clone(),
+            })
+        });
+        self.includes.add(&usage.includes, |token| {
+            processed.is_suppressed(&UnusedInclude {
+                token: token.clone(),
+            })
+        });
+    }
+
+    #[must_use]
+    /// [`UnusedMacro`] warnings for macros that were never used
+    pub fn macros(&self) -> Vec<Box<dyn Code>> {
+        self.macros
+            .unused()
+            .map(|token| {
+                Box::new(UnusedMacro {
+                    token: token.clone(),
+                }) as Box<dyn Code>
+            })
+            .collect()
+    }
+
+    #[must_use]
+    /// [`UnusedInclude`] warnings for includes that nothing was used from
+    pub fn includes(&self) -> Vec<Box<dyn Code>> {
+        self.includes
+            .unused()
+            .map(|token| {
+                Box::new(UnusedInclude {
+                    token: token.clone(),
+                }) as Box<dyn Code>
+            })
+            .collect()
+    }
+}
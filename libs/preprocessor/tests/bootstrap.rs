@@ -1,4 +1,7 @@
-use hemtt_preprocessor::Processor;
+use std::io::Write;
+
+use hemtt_common::reporting::Output;
+use hemtt_preprocessor::{IncludeCache, NoIncludes, Processor, Unused};
 
 const ROOT: &str = "tests/bootstrap/";
 
@@ -52,7 +55,10 @@ bootstrap!(define_single);
 bootstrap!(define_undef);
 bootstrap!(define_use_define);
 bootstrap!(define_with_dash);
+bootstrap!(eval_exec);
 bootstrap!(hashtag_outside_macro);
+bootstrap!(has_include);
+bootstrap!(if_expressions);
 bootstrap!(if_nested);
 bootstrap!(if_operators);
 bootstrap!(if_pass);
@@ -73,3 +79,289 @@ bootstrap!(sqf_select);
 bootstrap!(sqf);
 bootstrap!(strings);
 bootstrap!(utf);
+
+#[test]
+fn pre_workspace_defines() {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .memory()
+        .defines(vec![
+            ("DEBUG_MODE_FULL".to_string(), String::new()),
+            ("VERSION".to_string(), "2".to_string()),
+            ("VERSION".to_string(), "3".to_string()),
+        ])
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    source
+        .create_file()
+        .unwrap()
+        .write_all(b"#ifdef DEBUG_MODE_FULL\ndebug = 1;\n#endif\nversion = VERSION;\n")
+        .unwrap();
+    let processed = Processor::run(&source).unwrap();
+    assert_eq!(processed.as_string(), "debug = 1;\nversion = 3;\n");
+}
+
+#[test]
+fn pre_expansion() {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .memory()
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    source
+        .create_file()
+        .unwrap()
+        .write_all(b"#define QUOTE(x) #x\nvalue = QUOTE(hello);\n")
+        .unwrap();
+    let processed = Processor::run(&source).unwrap();
+    let Some(Output::Macro(token, _, expansion)) = processed
+        .output()
+        .iter()
+        .find(|o| matches!(o, Output::Macro(..)))
+    else {
+        panic!("expected a macro");
+    };
+    assert_eq!(token.to_string(), "QUOTE");
+    assert_eq!(token.position().start().line(), 2);
+    assert_eq!(expansion.definition.position().start().line(), 1);
+    assert_eq!(expansion.arguments.len(), 1);
+    assert_eq!(expansion.arguments[0][0].to_string(), "hello");
+}
+
+#[test]
+fn pre_include_cache() {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .memory()
+        .finish(None)
+        .unwrap();
+    let files = [
+        ("script_component.hpp", "#define PREFIX abe\n"),
+        ("a.hpp", "#include \"script_component.hpp\"\na = PREFIX;\n"),
+        ("b.hpp", "#include \"script_component.hpp\"\nb = PREFIX;\n"),
+    ];
+    for (name, content) in files {
+        workspace
+            .join(name)
+            .unwrap()
+            .create_file()
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+    }
+    let cache = IncludeCache::new();
+    let a = Processor::run_cached(&workspace.join("a.hpp").unwrap(), &cache).unwrap();
+    let b = Processor::run_cached(&workspace.join("b.hpp").unwrap(), &cache).unwrap();
+    assert_eq!(a.as_string(), "a = abe;\n");
+    assert_eq!(b.as_string(), "b = abe;\n");
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn pre_send_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Processor>();
+    assert_send_sync::<hemtt_common::reporting::Processed>();
+    assert_send_sync::<Output>();
+}
+
+#[test]
+fn pre_includes() {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .memory()
+        .finish(None)
+        .unwrap();
+    let files = [
+        ("macros.hpp", "#define PREFIX abe\n"),
+        (
+            "script_component.hpp",
+            "#include \"macros.hpp\"\n#include \"macros.hpp\"\n",
+        ),
+        (
+            "source.hpp",
+            "#include \"script_component.hpp\"\na = PREFIX;\n",
+        ),
+    ];
+    for (name, content) in files {
+        workspace
+            .join(name)
+            .unwrap()
+            .create_file()
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+    }
+    let processed = Processor::run(&workspace.join("source.hpp").unwrap()).unwrap();
+    let includes = processed
+        .includes()
+        .iter()
+        .map(|(from, to)| (from.as_str(), to.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        includes,
+        vec![
+            ("/source.hpp", "/script_component.hpp"),
+            ("/script_component.hpp", "/macros.hpp"),
+            ("/script_component.hpp", "/macros.hpp"),
+        ]
+    );
+    let dependencies = processed
+        .dependencies()
+        .iter()
+        .map(|path| path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(dependencies, vec!["/script_component.hpp", "/macros.hpp"]);
+}
+
+#[test]
+fn pre_unused() {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .memory()
+        .finish(None)
+        .unwrap();
+    let files = [
+        (
+            "macros.hpp",
+            "#define USED_A 1\n#define USED_B\n#define UNUSED 3\n#pragma hemtt suppress pw3\n#define SUPPRESSED 4\n",
+        ),
+        ("empty.hpp", "#define ALSO_UNUSED\n"),
+        ("class.hpp", "class Included {};\n"),
+        (
+            "guarded.hpp",
+            "#ifndef GUARDED_HPP\n#define GUARDED_HPP\nclass Guarded {};\n#endif\n",
+        ),
+        (
+            "a.hpp",
+            "#include \"macros.hpp\"\n#include \"empty.hpp\"\n#include \"class.hpp\"\n#include \"guarded.hpp\"\na = USED_A;\n",
+        ),
+        ("b.hpp", "#include \"macros.hpp\"\n#ifdef USED_B\nb = 1;\n#endif\n"),
+    ];
+    for (name, content) in files {
+        workspace
+            .join(name)
+            .unwrap()
+            .create_file()
+            .unwrap()
+            .write_all(content.as_bytes())
+            .unwrap();
+    }
+    let mut unused = Unused::new();
+    unused.add(&Processor::run(&workspace.join("a.hpp").unwrap()).unwrap());
+    let macros = |unused: &Unused| {
+        unused
+            .macros()
+            .iter()
+            .map(|w| w.token().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(macros(&unused), vec!["USED_B", "UNUSED", "ALSO_UNUSED"]);
+    unused.add(&Processor::run(&workspace.join("b.hpp").unwrap()).unwrap());
+    assert_eq!(macros(&unused), vec!["UNUSED", "ALSO_UNUSED"]);
+    assert_eq!(
+        unused
+            .includes()
+            .iter()
+            .map(|w| w.token().unwrap().to_string())
+            .collect::<Vec<_>>(),
+        vec!["empty.hpp"]
+    );
+}
+
+#[test]
+fn pre_run_str() {
+    let files = std::collections::HashMap::from([
+        (
+            "addons/main/script_component.hpp".to_string(),
+            "#include \"\\include\\macros.hpp\"\n".to_string(),
+        ),
+        (
+            "include/macros.hpp".to_string(),
+            "#define GREETING(x) hello x\n".to_string(),
+        ),
+    ]);
+    let processed = Processor::run_str(
+        "addons/main/source.hpp",
+        "#include \"script_component.hpp\"\nvalue = GREETING(NAME);\n",
+        vec![("NAME".to_string(), "world".to_string())],
+        files,
+    )
+    .unwrap();
+    assert_eq!(processed.as_string(), "value = hello world;\n");
+    assert_eq!(
+        processed
+            .dependencies()
+            .iter()
+            .map(|path| path.as_str())
+            .collect::<Vec<_>>(),
+        vec!["/addons/main/script_component.hpp", "/include/macros.hpp"]
+    );
+    let missing = Processor::run_str(
+        "source.hpp",
+        "#include \"missing.hpp\"\n",
+        Vec::new(),
+        NoIncludes,
+    );
+    assert_eq!(missing.unwrap_err().get_code().unwrap().ident(), "PE12");
+}
+
+#[test]
+fn pre_run_str_has_include() {
+    let files = std::collections::HashMap::from([(
+        "include/optional.hpp".to_string(),
+        "#define OPTIONAL 1\n".to_string(),
+    )]);
+    let processed = Processor::run_str(
+        "addons/main/source.hpp",
+        "#if __has_include(\"\\include\\optional.hpp\")\nfound = 1;\n#endif\n#if __has_include(\"\\include\\missing.hpp\")\nmissing = 1;\n#endif\n",
+        Vec::new(),
+        files,
+    )
+    .unwrap();
+    assert_eq!(processed.as_string(), "found = 1;\n");
+}
+
+#[test]
+fn pre_builtin_project() {
+    let processed = Processor::run_str(
+        "source.hpp",
+        "date = __DATE_STR__;\nbuild = __GAME_BUILD__;\n",
+        vec![
+            (
+                "__DATE_STR__".to_string(),
+                "\"2020/10/28, 14:17:42\"".to_string(),
+            ),
+            ("__GAME_BUILD__".to_string(), "150957".to_string()),
+        ],
+        NoIncludes,
+    )
+    .unwrap();
+    assert_eq!(
+        processed.as_string(),
+        "date = \"2020/10/28, 14:17:42\";\nbuild = 150957;\n"
+    );
+    let redefined = Processor::run_str(
+        "source.hpp",
+        "#define __DATE_STR__ today\n",
+        Vec::new(),
+        NoIncludes,
+    );
+    assert_eq!(redefined.unwrap_err().get_code().unwrap().ident(), "PE6");
+}
+
+#[test]
+fn pre_line_markers() {
+    let files = std::collections::HashMap::from([(
+        "script_component.hpp".to_string(),
+        "#define DOUBLE(x) x * 2\n\nprivate _included = 1;\n".to_string(),
+    )]);
+    let processed = Processor::run_str(
+        "source.sqf",
+        "#include \"script_component.hpp\"\nprivate _a = DOUBLE(1);\n#define UNUSED\n\nprivate _b = 2;\n",
+        Vec::new(),
+        files,
+    )
+    .unwrap();
+    assert_eq!(
+        processed.as_string_with_lines(),
+        "#line 2 \"/script_component.hpp\"\n\nprivate _included = 1;\n#line 2 \"/source.sqf\"\nprivate _a = 1 * 2;\n\n\nprivate _b = 2;\n"
+    );
+}
@@ -0,0 +1,7 @@
+
+class RscText {
+    x = 0.08;
+    y = 0.25;
+    name = "rsc_text";
+    text = "__EVAL(1 + 1)";
+};
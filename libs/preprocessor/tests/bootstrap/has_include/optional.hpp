@@ -0,0 +1 @@
+#define OPTIONAL 1
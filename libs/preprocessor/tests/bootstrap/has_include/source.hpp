@@ -0,0 +1,15 @@
+#if __has_include("optional.hpp")
+#include "optional.hpp"
+#else
+#define OPTIONAL 0
+#endif
+
+#if __has_include("\z\cba\addons\main\script_macros_common.hpp")
+#define HAS_CBA 1
+#else
+#define HAS_CBA 0
+#endif
+
+#if __has_include(<optional.hpp>)
+value = OPTIONAL + HAS_CBA;
+#endif
@@ -0,0 +1,14 @@
+
+data = "and not";
+
+data = "or";
+
+data = "arithmetic";
+
+data = "unary";
+
+data = "string";
+
+data = "skipped";
+
+data = "continued";
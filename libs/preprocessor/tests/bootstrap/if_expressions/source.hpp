@@ -0,0 +1,35 @@
+#define MAJOR 2
+#define MINOR 10
+#define VERSION (MAJOR * 100 + MINOR)
+#define NAME "hemtt"
+
+#if defined(MAJOR) && !defined(PATCH)
+data = "and not";
+#endif
+
+#if defined PATCH || MINOR >= 10
+data = "or";
+#endif
+
+#if VERSION == 210 && (MINOR % 3 == 1)
+data = "arithmetic";
+#endif
+
+#if !(MAJOR > 2) && -MINOR < 0
+data = "unary";
+#endif
+
+#if NAME != "ace"
+data = "string";
+#endif
+
+#if __has_include("missing.hpp") && MISSING
+data = "short circuit";
+#else
+data = "skipped";
+#endif
+
+#if MAJOR - 2 || \
+    MINOR / 5 == 2
+data = "continued";
+#endif
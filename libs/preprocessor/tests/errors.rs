@@ -37,7 +37,11 @@ fn check(dir: &str) {
                 .unwrap()
                 .read_to_end(&mut expected)
                 .unwrap();
-            let error = e.get_code().unwrap().report_generate().unwrap();
+            let error = e
+                .get_codes()
+                .into_iter()
+                .map(|code| code.report_generate().unwrap())
+                .collect::<String>();
             if expected.is_empty() {
                 std::fs::write(folder.join("stderr.ansi"), error.replace('\r', "")).unwrap();
             }
@@ -67,3 +71,7 @@ bootstrap!(pe15_if_invalid_operator);
 bootstrap!(pe16_if_incompatible_types);
 bootstrap!(pe17_double_else);
 bootstrap!(pe18_eoi_ifstate);
+bootstrap!(pe19_pragma_unknown);
+bootstrap!(pe20_pragma_invalid_scope);
+bootstrap!(pe21_eval_invalid);
+bootstrap!(multiple_errors);
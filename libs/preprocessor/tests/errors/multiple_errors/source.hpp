@@ -0,0 +1,8 @@
+#define TWO(a,b) a b
+#define UNIT
+first = TWO(1);
+#if UNIT
+inside = 1;
+#endif
+second = TWO(1,2,3);
+fine = TWO(1,2);
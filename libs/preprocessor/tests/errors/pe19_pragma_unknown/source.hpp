@@ -0,0 +1 @@
+#pragma hemtt ignore pw1_redefine
@@ -0,0 +1 @@
+#pragma hemtt suppress pw1_redefine addon
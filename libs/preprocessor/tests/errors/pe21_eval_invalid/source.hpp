@@ -0,0 +1 @@
+value = __EVAL(_undefined * 2);
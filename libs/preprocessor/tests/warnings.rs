@@ -1,4 +1,4 @@
-use std::io::Read;
+use std::io::{Read, Write};
 
 use hemtt_preprocessor::Processor;
 
@@ -55,3 +55,24 @@ fn check(dir: &str) {
 }
 
 bootstrap!(pw1_redefine);
+bootstrap!(pw1_redefine_suppress);
+
+#[test]
+fn pre_warning_pw1_redefine_workspace() {
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .memory()
+        .defines(vec![("VERSION".to_string(), "2".to_string())])
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.hpp").unwrap();
+    source
+        .create_file()
+        .unwrap()
+        .write_all(b"value = 1;\n#define VERSION 3\nversion = VERSION;\n")
+        .unwrap();
+    let processed = Processor::run(&source).unwrap();
+    assert_eq!(processed.as_string(), "value = 1;\nversion = 3;\n");
+    let warning = processed.warnings()[0].report_generate().unwrap();
+    assert!(warning.contains("is defined by the project or on the command line"));
+    assert!(!warning.contains("previous definition here"));
+}
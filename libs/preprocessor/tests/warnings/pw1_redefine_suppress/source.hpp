@@ -0,0 +1,4 @@
+#define test 1
+#pragma hemtt suppress pw1_redefine
+#define test 2
+#define test 3
@@ -0,0 +1,19 @@
+//! # Analyze
+
+use hemtt_common::reporting::Code;
+
+use crate::{commands::Database, Statements};
+
+mod signatures;
+mod variables;
+
+/// Find the warnings in a parsed SQF file
+///
+/// Spans in the warnings are offsets into the [`Processed`](hemtt_common::reporting::Processed)
+/// the statements were parsed from
+#[must_use]
+pub fn analyze(statements: &Statements, database: &Database) -> Vec<Box<dyn Code>> {
+    let mut warnings = variables::analyze(statements);
+    warnings.extend(signatures::analyze(statements, database));
+    warnings
+}
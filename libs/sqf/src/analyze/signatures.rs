@@ -0,0 +1,191 @@
+//! Lints on how commands are used, checked against the signatures in the [`Database`]
+//!
+//! The type of an argument is only known for literals and the results of comparisons,
+//! anything else could be any type
+
+use hemtt_common::reporting::Code;
+
+use crate::{
+    codes::{
+        sw6_unknown_command::UnknownCommand, sw7_wrong_arity::WrongArity,
+        sw8_type_mismatch::TypeMismatch,
+    },
+    commands::{Database, Type},
+    Expression, Statement, Statements,
+};
+
+pub fn analyze(statements: &Statements, database: &Database) -> Vec<Box<dyn Code>> {
+    let mut warnings = Vec::new();
+    walk_statements(statements, database, &mut warnings);
+    warnings
+}
+
+fn walk_statements(
+    statements: &Statements,
+    database: &Database,
+    warnings: &mut Vec<Box<dyn Code>>,
+) {
+    for statement in statements.content() {
+        match statement {
+            Statement::Assign { value, .. } => walk(value, database, warnings),
+            Statement::Expression(expression) => walk(expression, database, warnings),
+        }
+    }
+}
+
+fn walk(expression: &Expression, database: &Database, warnings: &mut Vec<Box<dyn Code>>) {
+    match expression {
+        Expression::Code(statements) => walk_statements(statements, database, warnings),
+        Expression::Array(items, _) => {
+            for item in items {
+                walk(item, database, warnings);
+            }
+        }
+        Expression::UnaryCommand(command, argument, _) => {
+            walk(argument, database, warnings);
+            let Some(signatures) = database.unary(command.as_str()) else {
+                if database.binary(command.as_str()).is_some() {
+                    warnings.push(Box::new(WrongArity::new(command.clone(), true)));
+                } else if database.is_nular(command.as_str()) {
+                    warnings.push(Box::new(WrongArity::new(command.clone(), false)));
+                } else {
+                    warnings.push(Box::new(UnknownCommand::new(command.clone())));
+                }
+                return;
+            };
+            let Some(found) = type_of(argument) else {
+                return;
+            };
+            if !signatures.iter().any(|expected| expected.accepts(&found)) {
+                warnings.push(Box::new(TypeMismatch::new(
+                    command.clone(),
+                    argument.span(),
+                    signatures.to_vec(),
+                    found,
+                )));
+            }
+        }
+        Expression::BinaryCommand(command, left, right, _) => {
+            walk(left, database, warnings);
+            walk(right, database, warnings);
+            let Some(signatures) = database.binary(command.as_str()) else {
+                return;
+            };
+            let left_type = type_of(left);
+            let right_type = type_of(right);
+            let matching = signatures
+                .iter()
+                .filter(|(expected, _)| left_type.iter().all(|t| expected.accepts(t)))
+                .collect::<Vec<_>>();
+            if matching.is_empty() {
+                if let Some(found) = left_type {
+                    warnings.push(Box::new(TypeMismatch::new(
+                        command.clone(),
+                        left.span(),
+                        signatures.iter().map(|(left, _)| left.clone()).collect(),
+                        found,
+                    )));
+                }
+                return;
+            }
+            let Some(found) = right_type else {
+                return;
+            };
+            if !matching
+                .iter()
+                .any(|(_, expected)| expected.accepts(&found))
+            {
+                warnings.push(Box::new(TypeMismatch::new(
+                    command.clone(),
+                    right.span(),
+                    matching.iter().map(|(_, right)| right.clone()).collect(),
+                    found,
+                )));
+            }
+        }
+        Expression::String(..)
+        | Expression::Number(..)
+        | Expression::Variable(_)
+        | Expression::NularCommand(_) => {}
+    }
+}
+
+/// The type of an expression, if it is known without running it
+fn type_of(expression: &Expression) -> Option<Type> {
+    match expression {
+        Expression::Code(_) => Some(Type::Code),
+        Expression::String(..) => Some(Type::String),
+        Expression::Number(..) => Some(Type::Scalar),
+        Expression::Array(..) => Some(Type::Array),
+        Expression::NularCommand(command) => ["true", "false"]
+            .iter()
+            .any(|name| command.as_str().eq_ignore_ascii_case(name))
+            .then_some(Type::Bool),
+        Expression::UnaryCommand(command, _, _) => ["!", "not"]
+            .iter()
+            .any(|name| command.as_str().eq_ignore_ascii_case(name))
+            .then_some(Type::Bool),
+        Expression::BinaryCommand(command, _, _, _) => {
+            ["==", "!=", ">", "<", ">=", "<=", "&&", "||", "and", "or"]
+                .iter()
+                .any(|name| command.as_str().eq_ignore_ascii_case(name))
+                .then_some(Type::Bool)
+        }
+        Expression::Variable(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_preprocessor::{NoIncludes, Processor};
+
+    use crate::commands::Database;
+
+    fn idents(source: &str, database: &Database) -> Vec<&'static str> {
+        let processed = Processor::run_str("/test.sqf", source, Vec::new(), NoIncludes).unwrap();
+        let statements = crate::parse(&processed, database).unwrap();
+        super::analyze(&statements, database)
+            .iter()
+            .map(|warning| warning.ident())
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        assert!(idents(
+            r#"
+            private _list = [1, 2, 3] select [0, 2];
+            private _count = count _list + count "abc";
+            if (_count > 2 && {!(_list isEqualTo [])}) then {
+                hint format ["%1", _list select 0];
+            };
+            _list sort true;
+            "#,
+            Database::builtin()
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(idents("ext_call \"a\"", Database::builtin()), vec!["SW6"]);
+        let database = Database::with_definitions("u:ext_call STRING").unwrap();
+        assert!(idents("ext_call \"a\"", &database).is_empty());
+        assert_eq!(idents("ext_call 1", &database), vec!["SW8"]);
+    }
+
+    #[test]
+    fn arity() {
+        assert_eq!(idents("setDamage 1", Database::builtin()), vec!["SW7"]);
+    }
+
+    #[test]
+    fn types() {
+        assert_eq!(idents("count 5", Database::builtin()), vec!["SW8"]);
+        assert_eq!(idents("_a select \"b\"", Database::builtin()), vec!["SW8"]);
+        assert!(idents("\"a\" select 1", Database::builtin()).is_empty());
+        assert_eq!(idents("_a == true", Database::builtin()), vec!["SW8"]);
+        assert_eq!(idents("[1] == _a", Database::builtin()), vec!["SW8"]);
+        assert_eq!(idents("1 + \"a\"", Database::builtin()), vec!["SW8"]);
+    }
+}
@@ -0,0 +1,401 @@
+//! Lints on the use of local variables, SQF variable names are case insensitive
+
+use std::collections::HashMap;
+
+use hemtt_common::reporting::Code;
+
+use crate::{
+    codes::{
+        sw1_undefined_variable::UndefinedVariable, sw2_unused_private::UnusedPrivate,
+        sw3_assign_this::AssignThis, sw4_shadowed_param::ShadowedParam,
+        sw5_private_in_loop::PrivateInLoop,
+    },
+    Expression, Ident, Statement, Statements,
+};
+
+/// Local variables that are set by the engine, or by common macros
+const MAGIC: &[&str] = &[
+    "_this",
+    "_x",
+    "_y",
+    "_foreachindex",
+    "_exception",
+    "_thisscript",
+    "_thisfsm",
+    "_thisevent",
+    "_thiseventhandler",
+    "_thisargs",
+    "_thisscriptedeventhandler",
+    "_time",
+    "_fnc_scriptname",
+    "_fnc_scriptnameparent",
+];
+
+pub fn analyze(statements: &Statements) -> Vec<Box<dyn Code>> {
+    let mut analyzer = Analyzer::default();
+    analyzer.statements(statements, false);
+    analyzer.warnings
+}
+
+struct Variable {
+    ident: Ident,
+    private: bool,
+    param: bool,
+    used: bool,
+}
+
+#[derive(Default)]
+struct Scope {
+    variables: HashMap<String, Variable>,
+    in_loop: bool,
+}
+
+#[derive(Default)]
+struct Analyzer {
+    scopes: Vec<Scope>,
+    warnings: Vec<Box<dyn Code>>,
+}
+
+impl Analyzer {
+    fn statements(&mut self, statements: &Statements, loop_body: bool) {
+        let in_loop = loop_body || self.scopes.last().is_some_and(|scope| scope.in_loop);
+        self.scopes.push(Scope {
+            variables: HashMap::new(),
+            in_loop,
+        });
+        self.inline(statements);
+        let scope = self.scopes.pop().expect("scope was pushed");
+        let mut unused = scope
+            .variables
+            .into_values()
+            .filter(|var| var.private && !var.used && !is_magic(&var.ident))
+            .collect::<Vec<_>>();
+        unused.sort_by_key(|var| var.ident.span.start);
+        for var in unused {
+            self.warnings.push(Box::new(UnusedPrivate::new(var.ident)));
+        }
+    }
+
+    /// Walk statements in the current scope
+    fn inline(&mut self, statements: &Statements) {
+        for statement in statements.content() {
+            match statement {
+                Statement::Assign {
+                    name,
+                    value,
+                    private,
+                    ..
+                } => {
+                    self.expression(value);
+                    self.assign(name, *private);
+                }
+                Statement::Expression(expression) => self.expression(expression),
+            }
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Code(statements) => self.statements(statements, false),
+            Expression::String(value, _) => self.mention(value),
+            Expression::Number(..) | Expression::NularCommand(_) => {}
+            Expression::Array(items, _) => {
+                for item in items {
+                    self.expression(item);
+                }
+            }
+            Expression::Variable(ident) => self.read(ident),
+            Expression::UnaryCommand(command, argument, _) => {
+                match command.as_str().to_ascii_lowercase().as_str() {
+                    "private" => self.private(argument),
+                    "params" => self.params(argument),
+                    "for" => self.for_header(argument),
+                    _ => self.expression(argument),
+                }
+            }
+            Expression::BinaryCommand(command, left, right, _) => {
+                match command.as_str().to_ascii_lowercase().as_str() {
+                    "params" => {
+                        self.expression(left);
+                        self.params(right);
+                    }
+                    "do" if is_loop_header(left) => {
+                        self.expression(left);
+                        self.loop_body(right);
+                    }
+                    _ => {
+                        self.expression(left);
+                        self.expression(right);
+                    }
+                }
+            }
+        }
+    }
+
+    fn loop_body(&mut self, body: &Expression) {
+        if let Expression::Code(statements) = body {
+            self.statements(statements, true);
+        } else {
+            self.expression(body);
+        }
+    }
+
+    /// `for "_i"` declares the counter, `for [{...}, {...}, {...}]` runs its code in the loop scope
+    fn for_header(&mut self, argument: &Expression) {
+        match argument {
+            Expression::String(name, span) => self.assign(
+                &Ident {
+                    value: name.clone(),
+                    span: span.clone(),
+                },
+                false,
+            ),
+            Expression::Array(items, _) => {
+                for item in items {
+                    if let Expression::Code(statements) = item {
+                        self.inline(statements);
+                    } else {
+                        self.expression(item);
+                    }
+                }
+            }
+            _ => self.expression(argument),
+        }
+    }
+
+    /// `private _a`, `private "_a"` or `private ["_a", "_b"]`
+    fn private(&mut self, argument: &Expression) {
+        match argument {
+            Expression::Variable(ident) => self.declare(ident, true, false),
+            Expression::String(..) => {
+                if let Some(ident) = string_ident(argument) {
+                    self.declare(&ident, true, false);
+                }
+            }
+            Expression::Array(items, _)
+                if items.iter().all(|item| string_ident(item).is_some()) =>
+            {
+                for ident in items.iter().filter_map(string_ident) {
+                    self.declare(&ident, true, false);
+                }
+            }
+            _ => self.expression(argument),
+        }
+    }
+
+    /// `params ["_a", ["_b", default]]`
+    fn params(&mut self, argument: &Expression) {
+        let Expression::Array(items, _) = argument else {
+            self.expression(argument);
+            return;
+        };
+        for item in items {
+            let name = match item {
+                Expression::Array(parts, _) => {
+                    for part in parts.iter().skip(1) {
+                        self.expression(part);
+                    }
+                    parts.first().and_then(string_ident)
+                }
+                _ => string_ident(item),
+            };
+            if let Some(ident) = name {
+                self.declare(&ident, false, true);
+            }
+        }
+    }
+
+    fn assign(&mut self, name: &Ident, private: bool) {
+        if name.as_str().eq_ignore_ascii_case("_this") {
+            self.warnings.push(Box::new(AssignThis::new(name.clone())));
+            return;
+        }
+        if !name.is_local() {
+            return;
+        }
+        if private {
+            self.declare(name, true, false);
+        } else if self.find(name.as_str()).is_none() {
+            self.declare(name, false, false);
+        }
+    }
+
+    fn declare(&mut self, ident: &Ident, private: bool, param: bool) {
+        let key = ident.as_str().to_ascii_lowercase();
+        let shadowed = if private {
+            self.scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.variables.get(&key).filter(|var| var.param))
+        } else if param {
+            self.scopes
+                .last()
+                .and_then(|scope| scope.variables.get(&key).filter(|var| var.param))
+        } else {
+            None
+        };
+        if let Some(shadowed) = shadowed {
+            self.warnings.push(Box::new(ShadowedParam::new(
+                ident.clone(),
+                shadowed.ident.clone(),
+            )));
+        }
+        let scope = self.scopes.last_mut().expect("always in a scope");
+        if private && scope.in_loop {
+            self.warnings
+                .push(Box::new(PrivateInLoop::new(ident.clone())));
+        }
+        scope.variables.insert(
+            key,
+            Variable {
+                ident: ident.clone(),
+                private,
+                param,
+                used: false,
+            },
+        );
+    }
+
+    fn find(&mut self, name: &str) -> Option<&mut Variable> {
+        let key = name.to_ascii_lowercase();
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.variables.get_mut(&key))
+    }
+
+    fn read(&mut self, ident: &Ident) {
+        if !ident.is_local() || is_magic(ident) {
+            return;
+        }
+        if let Some(var) = self.find(ident.as_str()) {
+            var.used = true;
+            return;
+        }
+        self.warnings
+            .push(Box::new(UndefinedVariable::new(ident.clone())));
+        // only report the first read in a scope
+        self.declare(ident, false, false);
+    }
+
+    /// A string with the name of a variable, `isNil "_a"`, counts as using it
+    fn mention(&mut self, value: &str) {
+        if !value.starts_with('_') {
+            return;
+        }
+        if let Some(var) = self.find(value) {
+            var.used = true;
+        }
+    }
+}
+
+fn is_magic(ident: &Ident) -> bool {
+    MAGIC.contains(&ident.as_str().to_ascii_lowercase().as_str())
+}
+
+/// `while {...}`, or `for "_i" from 0 to 10 step 2`
+fn is_loop_header(expression: &Expression) -> bool {
+    match expression {
+        Expression::UnaryCommand(command, _, _) => {
+            command.as_str().eq_ignore_ascii_case("while")
+                || command.as_str().eq_ignore_ascii_case("for")
+        }
+        Expression::BinaryCommand(command, left, _, _) => {
+            ["from", "to", "step"]
+                .iter()
+                .any(|name| command.as_str().eq_ignore_ascii_case(name))
+                && is_loop_header(left)
+        }
+        _ => false,
+    }
+}
+
+/// A string that names a local variable, spanning the string
+fn string_ident(expression: &Expression) -> Option<Ident> {
+    let Expression::String(value, span) = expression else {
+        return None;
+    };
+    value.starts_with('_').then(|| Ident {
+        value: value.clone(),
+        span: span.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_preprocessor::{NoIncludes, Processor};
+
+    use crate::commands::Database;
+
+    fn idents(source: &str) -> Vec<&'static str> {
+        let processed = Processor::run_str("/test.sqf", source, Vec::new(), NoIncludes).unwrap();
+        let statements = crate::parse(&processed, Database::builtin()).unwrap();
+        super::analyze(&statements)
+            .iter()
+            .map(|warning| warning.ident())
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        assert!(idents(
+            r#"
+            params ["_unit", ["_count", 1]];
+            private _total = _count;
+            private "_other";
+            if (isNil "_other") then { _other = 1 };
+            { _total = _total + _x } forEach [1, 2];
+            for "_i" from 0 to 10 do { _total = _total + _i };
+            [_unit, _total]
+            "#
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn undefined() {
+        assert_eq!(idents("_a + _A"), vec!["SW1"]);
+        assert_eq!(idents("if (true) then { _a = 1 }; _a"), vec!["SW1"]);
+        assert!(idents("private _a = 1; if (true) then { _a = 2 }; _a").is_empty());
+        assert!(idents("for [{_i = 0}, {_i < 10}, {_i = _i + 1}] do {}").is_empty());
+    }
+
+    #[test]
+    fn unused() {
+        assert_eq!(idents("private _a = 1; private _b = 2; _b"), vec!["SW2"]);
+        assert_eq!(idents(r#"private ["_a", "_b"]; _a"#), vec!["SW2"]);
+        assert!(idents("private _a = 1; { hint str _a } forEach []").is_empty());
+    }
+
+    #[test]
+    fn assign_this() {
+        assert_eq!(idents("_THIS = 1"), vec!["SW3"]);
+        assert_eq!(idents("private _this = 1; _this"), vec!["SW3"]);
+    }
+
+    #[test]
+    fn shadowed_param() {
+        assert_eq!(
+            idents(r#"params ["_unit"]; if (true) then { private _unit = 1; _unit }"#),
+            vec!["SW4"]
+        );
+        assert_eq!(idents(r#"params ["_a"]; params ["_a"]; _a"#), vec!["SW4"]);
+        assert!(
+            idents(r#"params ["_a"]; _a addEventHandler ["Killed", { params ["_a"]; _a }]"#)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn private_in_loop() {
+        assert_eq!(
+            idents("while {true} do { private _a = 1; _a }"),
+            vec!["SW5"]
+        );
+        assert_eq!(
+            idents(r#"for "_i" from 0 to 1 step 1 do { if (true) then { private _a = _i; _a } }"#),
+            vec!["SW5"]
+        );
+        assert!(idents("{ private _a = _x; _a } forEach []").is_empty());
+    }
+}
@@ -0,0 +1,62 @@
+use std::{fmt::Display, hash::Hash};
+
+use chumsky::error::{Simple, SimpleReason};
+use hemtt_common::reporting::{Mapping, Processed};
+
+pub mod se1_invalid_token;
+pub mod se2_unexpected_token;
+pub mod sw10_unused_function;
+pub mod sw1_undefined_variable;
+pub mod sw2_unused_private;
+pub mod sw3_assign_this;
+pub mod sw4_shadowed_param;
+pub mod sw5_private_in_loop;
+pub mod sw6_unknown_command;
+pub mod sw7_wrong_arity;
+pub mod sw8_type_mismatch;
+pub mod sw9_missing_function;
+
+/// The mapping of an offset, errors at the end of the file use the last token
+fn mapping(processed: &Processed, offset: usize) -> Option<&Mapping> {
+    if offset < processed.as_string().len() {
+        return processed.mapping(offset);
+    }
+    processed
+        .mappings(processed.as_string().len().checked_sub(1)?)
+        .into_iter()
+        .max_by_key(|map| map.processed_start().offset())
+}
+
+/// What was found, and what was expected instead
+fn describe<T: Display + Hash + Eq>(error: &Simple<T>) -> (String, String) {
+    let found = error
+        .found()
+        .map_or_else(|| "end of file".to_string(), |found| format!("`{found}`"));
+    match error.reason() {
+        SimpleReason::Custom(message) => (message.clone(), message.clone()),
+        SimpleReason::Unclosed { delimiter, .. } => (
+            format!("unclosed `{delimiter}`"),
+            format!("found {found} before `{delimiter}` was closed"),
+        ),
+        SimpleReason::Unexpected => {
+            // only closing delimiters are worth suggesting, the rest of the expected
+            // tokens are an incomplete list of everything that could start an expression
+            let mut expected = error
+                .expected()
+                .filter_map(|expected| {
+                    let expected = expected.as_ref()?.to_string();
+                    ["]", "}", ")"]
+                        .contains(&expected.as_str())
+                        .then(|| format!("`{expected}`"))
+                })
+                .collect::<Vec<_>>();
+            expected.sort();
+            let label = if expected.is_empty() {
+                format!("unexpected {found}")
+            } else {
+                format!("expected {}", expected.join(" or "))
+            };
+            (format!("unexpected {found}"), label)
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::ops::Range;
+
+use ariadne::{sources, ColorGenerator, Label, Report};
+use chumsky::error::Simple;
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::parse::Token;
+
+use super::{describe, mapping};
+
+pub struct UnexpectedToken {
+    span: Range<usize>,
+    message: String,
+    label: String,
+}
+
+impl UnexpectedToken {
+    pub fn new(error: &Simple<Token>) -> Self {
+        let (message, label) = describe(error);
+        Self {
+            span: error.span(),
+            message,
+            label,
+        }
+    }
+}
+
+impl Code for UnexpectedToken {
+    fn ident(&self) -> &'static str {
+        "SE2"
+    }
+
+    fn message(&self) -> String {
+        self.message.clone()
+    }
+
+    fn label_message(&self) -> String {
+        self.label.clone()
+    }
+
+    fn help(&self) -> Option<String> {
+        None
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = mapping(processed, self.span.start)?;
+        let map_file = processed.source(map.source())?;
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let a = colors.next();
+        Report::build(
+            ariadne::ReportKind::Error,
+            map_file.0.clone(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                map_file.0.clone(),
+                map.original_column()..map.original_column() + self.span.len(),
+            ))
+            .with_message(self.label_message())
+            .with_color(a),
+        )
+        .finish()
+        .write_for_stdout(sources(processed.sources()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let Some(map) = mapping(processed, self.span.start) else {
+            return vec![];
+        };
+        let Some(map_file) = processed.source(map.source()) else {
+            return vec![];
+        };
+        vec![self.annotation(
+            AnnotationLevel::Error,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        )]
+    }
+}
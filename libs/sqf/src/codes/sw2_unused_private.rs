@@ -0,0 +1,76 @@
+use ariadne::{sources, ColorGenerator, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::Ident;
+
+use super::mapping;
+
+pub struct UnusedPrivate {
+    ident: Ident,
+}
+
+impl UnusedPrivate {
+    pub const fn new(ident: Ident) -> Self {
+        Self { ident }
+    }
+}
+
+impl Code for UnusedPrivate {
+    fn ident(&self) -> &'static str {
+        "SW2"
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` is never used", self.ident.as_str())
+    }
+
+    fn label_message(&self) -> String {
+        "declared here".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("remove the variable, or use it".to_string())
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = mapping(processed, self.ident.span.start)?;
+        let map_file = processed.source(map.source())?;
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let a = colors.next();
+        Report::build(
+            ariadne::ReportKind::Warning,
+            map_file.0.clone(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                map_file.0.clone(),
+                map.original_column()..map.original_column() + self.ident.span.len(),
+            ))
+            .with_message(self.label_message())
+            .with_color(a),
+        )
+        .with_help(self.help().unwrap())
+        .finish()
+        .write_for_stdout(sources(processed.sources()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let Some(map) = mapping(processed, self.ident.span.start) else {
+            return vec![];
+        };
+        let Some(map_file) = processed.source(map.source()) else {
+            return vec![];
+        };
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        )]
+    }
+}
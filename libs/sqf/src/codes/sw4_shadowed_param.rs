@@ -0,0 +1,88 @@
+use ariadne::{sources, ColorGenerator, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::Ident;
+
+use super::mapping;
+
+pub struct ShadowedParam {
+    ident: Ident,
+    param: Ident,
+}
+
+impl ShadowedParam {
+    pub const fn new(ident: Ident, param: Ident) -> Self {
+        Self { ident, param }
+    }
+}
+
+impl Code for ShadowedParam {
+    fn ident(&self) -> &'static str {
+        "SW4"
+    }
+
+    fn message(&self) -> String {
+        format!("`{}` shadows a parameter", self.ident.as_str())
+    }
+
+    fn label_message(&self) -> String {
+        "declared again here".to_string()
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("use a different name, or assign to the parameter without `private`".to_string())
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = mapping(processed, self.ident.span.start)?;
+        let map_file = processed.source(map.source())?;
+        let param_map = mapping(processed, self.param.span.start)?;
+        let param_file = processed.source(param_map.source())?;
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let a = colors.next();
+        let b = colors.next();
+        Report::build(
+            ariadne::ReportKind::Warning,
+            map_file.0.clone(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                map_file.0.clone(),
+                map.original_column()..map.original_column() + self.ident.span.len(),
+            ))
+            .with_message(self.label_message())
+            .with_color(a),
+        )
+        .with_label(
+            Label::new((
+                param_file.0.clone(),
+                param_map.original_column()..param_map.original_column() + self.param.span.len(),
+            ))
+            .with_message("parameter defined here")
+            .with_color(b),
+        )
+        .with_help(self.help().unwrap())
+        .finish()
+        .write_for_stdout(sources(processed.sources()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let Some(map) = mapping(processed, self.ident.span.start) else {
+            return vec![];
+        };
+        let Some(map_file) = processed.source(map.source()) else {
+            return vec![];
+        };
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        )]
+    }
+}
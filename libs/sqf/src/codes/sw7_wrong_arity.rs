@@ -0,0 +1,92 @@
+use ariadne::{sources, ColorGenerator, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::Ident;
+
+use super::mapping;
+
+pub struct WrongArity {
+    ident: Ident,
+    binary: bool,
+}
+
+impl WrongArity {
+    pub const fn new(ident: Ident, binary: bool) -> Self {
+        Self { ident, binary }
+    }
+}
+
+impl Code for WrongArity {
+    fn ident(&self) -> &'static str {
+        "SW7"
+    }
+
+    fn message(&self) -> String {
+        format!(
+            "`{}` is used with the wrong number of arguments",
+            self.ident.as_str()
+        )
+    }
+
+    fn label_message(&self) -> String {
+        if self.binary {
+            "needs an argument on both sides".to_string()
+        } else {
+            "does not take an argument".to_string()
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        Some(if self.binary {
+            format!(
+                "`{}` is a binary command, `left {} right`",
+                self.ident.as_str(),
+                self.ident.as_str()
+            )
+        } else {
+            format!("`{}` is a nular command", self.ident.as_str())
+        })
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = mapping(processed, self.ident.span.start)?;
+        let map_file = processed.source(map.source())?;
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let a = colors.next();
+        Report::build(
+            ariadne::ReportKind::Warning,
+            map_file.0.clone(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                map_file.0.clone(),
+                map.original_column()..map.original_column() + self.ident.span.len(),
+            ))
+            .with_message(self.label_message())
+            .with_color(a),
+        )
+        .with_help(self.help().unwrap())
+        .finish()
+        .write_for_stdout(sources(processed.sources()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let Some(map) = mapping(processed, self.ident.span.start) else {
+            return vec![];
+        };
+        let Some(map_file) = processed.source(map.source()) else {
+            return vec![];
+        };
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        )]
+    }
+}
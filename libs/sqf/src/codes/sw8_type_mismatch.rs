@@ -0,0 +1,106 @@
+use std::ops::Range;
+
+use ariadne::{sources, ColorGenerator, Label, Report};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Processed};
+
+use crate::{commands::Type, Ident};
+
+use super::mapping;
+
+pub struct TypeMismatch {
+    command: Ident,
+    span: Range<usize>,
+    expected: Vec<Type>,
+    found: Type,
+}
+
+impl TypeMismatch {
+    pub fn new(command: Ident, span: Range<usize>, expected: Vec<Type>, found: Type) -> Self {
+        let mut unique = Vec::new();
+        for t in expected {
+            if !unique.contains(&t) {
+                unique.push(t);
+            }
+        }
+        Self {
+            command,
+            span,
+            expected: unique,
+            found,
+        }
+    }
+}
+
+impl Code for TypeMismatch {
+    fn ident(&self) -> &'static str {
+        "SW8"
+    }
+
+    fn message(&self) -> String {
+        format!("wrong argument type for `{}`", self.command.as_str())
+    }
+
+    fn label_message(&self) -> String {
+        format!("found {}", self.found)
+    }
+
+    fn help(&self) -> Option<String> {
+        let mut expected = self
+            .expected
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        let last = expected.pop().unwrap_or_default();
+        let expected = if expected.is_empty() {
+            last
+        } else {
+            format!("{} or {last}", expected.join(", "))
+        };
+        Some(format!(
+            "`{}` expects {expected} here",
+            self.command.as_str()
+        ))
+    }
+
+    fn report_generate_processed(&self, processed: &Processed) -> Option<String> {
+        let map = mapping(processed, self.span.start)?;
+        let map_file = processed.source(map.source())?;
+        let mut out = Vec::new();
+        let mut colors = ColorGenerator::new();
+        let a = colors.next();
+        Report::build(
+            ariadne::ReportKind::Warning,
+            map_file.0.clone(),
+            map.original_column(),
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((
+                map_file.0.clone(),
+                map.original_column()..map.original_column() + self.span.len(),
+            ))
+            .with_message(self.label_message())
+            .with_color(a),
+        )
+        .with_help(self.help().unwrap())
+        .finish()
+        .write_for_stdout(sources(processed.sources()), &mut out)
+        .unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+
+    fn ci_generate_processed(&self, processed: &Processed) -> Vec<Annotation> {
+        let Some(map) = mapping(processed, self.span.start) else {
+            return vec![];
+        };
+        let Some(map_file) = processed.source(map.source()) else {
+            return vec![];
+        };
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            map_file.0.as_str().to_string(),
+            map.original(),
+        )]
+    }
+}
@@ -0,0 +1,83 @@
+use std::sync::Arc;
+
+use ariadne::{sources, ColorGenerator, Label, Report, ReportKind};
+use hemtt_common::reporting::{Annotation, AnnotationLevel, Code, Token};
+
+pub struct MissingFunction {
+    /// The macro referencing the function
+    token: Arc<Token>,
+    addon: String,
+    name: String,
+}
+
+impl MissingFunction {
+    pub const fn new(token: Arc<Token>, addon: String, name: String) -> Self {
+        Self { token, addon, name }
+    }
+}
+
+impl Code for MissingFunction {
+    fn ident(&self) -> &'static str {
+        "SW9"
+    }
+
+    fn token(&self) -> Option<&Token> {
+        Some(&self.token)
+    }
+
+    fn message(&self) -> String {
+        format!("function `{}` not found", self.name)
+    }
+
+    fn label_message(&self) -> String {
+        format!(
+            "no `fnc_{}.sqf` or `PREP({})` in `{}`",
+            self.name, self.name, self.addon
+        )
+    }
+
+    fn help(&self) -> Option<String> {
+        Some("check the name of the function and its addon".to_string())
+    }
+
+    fn report_generate(&self) -> Option<String> {
+        let mut colors = ColorGenerator::default();
+        let mut out = Vec::new();
+        let span = self.token.position().span();
+        Report::build(
+            ReportKind::Warning,
+            self.token.position().path().as_str(),
+            span.start,
+        )
+        .with_code(self.ident())
+        .with_message(self.message())
+        .with_label(
+            Label::new((self.token.position().path().to_string(), span))
+                .with_color(colors.next())
+                .with_message(self.label_message()),
+        )
+        .with_help(self.help().expect("help should be Some"))
+        .finish()
+        .write_for_stdout(
+            sources(vec![(
+                self.token.position().path().to_string(),
+                self.token
+                    .position()
+                    .path()
+                    .read_to_string()
+                    .unwrap_or_default(),
+            )]),
+            &mut out,
+        )
+        .ok()?;
+        String::from_utf8(out).ok()
+    }
+
+    fn ci_generate(&self) -> Vec<Annotation> {
+        vec![self.annotation(
+            AnnotationLevel::Warning,
+            self.token.position().path().as_str().to_string(),
+            self.token.position(),
+        )]
+    }
+}
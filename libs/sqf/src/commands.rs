@@ -0,0 +1,263 @@
+//! Names and signatures of SQF commands
+//!
+//! SQF commands are identifiers, so the parser can only tell a binary command from a
+//! variable, or a unary command from a nular command, by name. Names are lowercase.
+//!
+//! The built in commands are read from `data/commands.txt`, in the format of `supportInfo ""`.
+//! Projects can add their own, for commands from extensions, in the same format.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    sync::OnceLock,
+};
+
+use crate::Error;
+
+const BUILTIN: &str = include_str!("../data/commands.txt");
+
+const PRECEDENCE: &[(&str, Precedence)] = &[
+    ("#", Precedence::Select),
+    ("^", Precedence::Power),
+    ("*", Precedence::Multiply),
+    ("/", Precedence::Multiply),
+    ("%", Precedence::Multiply),
+    ("mod", Precedence::Multiply),
+    ("atan2", Precedence::Multiply),
+    ("+", Precedence::Add),
+    ("-", Precedence::Add),
+    ("max", Precedence::Add),
+    ("min", Precedence::Add),
+    ("else", Precedence::Else),
+    ("==", Precedence::Compare),
+    ("!=", Precedence::Compare),
+    (">", Precedence::Compare),
+    ("<", Precedence::Compare),
+    (">=", Precedence::Compare),
+    ("<=", Precedence::Compare),
+    (">>", Precedence::Compare),
+    ("&&", Precedence::And),
+    ("and", Precedence::And),
+    ("||", Precedence::Or),
+    ("or", Precedence::Or),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// The precedence of a binary command, from the loosest to the tightest
+pub enum Precedence {
+    /// `||` and `or`
+    Or,
+    /// `&&` and `and`
+    And,
+    /// `==`, `!=`, `>`, `<`, `>=`, `<=` and `>>`
+    Compare,
+    /// Every binary command that is not in another group
+    Binary,
+    /// `else`
+    Else,
+    /// `+`, `-`, `max` and `min`
+    Add,
+    /// `*`, `/`, `%`, `mod` and `atan2`
+    Multiply,
+    /// `^`
+    Power,
+    /// `#`
+    Select,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// The type of a command argument
+pub enum Type {
+    /// Any type is accepted
+    Any,
+    /// `ARRAY`
+    Array,
+    /// `BOOL`
+    Bool,
+    /// `CODE`
+    Code,
+    /// `SCALAR`, a number
+    Scalar,
+    /// `STRING`
+    String,
+    /// Any other type, by its name in `supportInfo`
+    Other(String),
+}
+
+impl Type {
+    fn from_name(name: &str) -> Self {
+        match name {
+            "ANY" => Self::Any,
+            "ARRAY" => Self::Array,
+            "BOOL" => Self::Bool,
+            "CODE" => Self::Code,
+            "SCALAR" => Self::Scalar,
+            "STRING" => Self::String,
+            _ => Self::Other(name.to_string()),
+        }
+    }
+
+    #[must_use]
+    /// Can a value of the other type be used where this type is expected
+    pub fn accepts(&self, other: &Self) -> bool {
+        matches!(self, Self::Any) || matches!(other, Self::Any) || self == other
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Any => write!(f, "ANY"),
+            Self::Array => write!(f, "ARRAY"),
+            Self::Bool => write!(f, "BOOL"),
+            Self::Code => write!(f, "CODE"),
+            Self::Scalar => write!(f, "SCALAR"),
+            Self::String => write!(f, "STRING"),
+            Self::Other(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// The known commands, and the arguments they accept
+pub struct Database {
+    nular: HashSet<String>,
+    unary: HashMap<String, Vec<Type>>,
+    binary: HashMap<String, Vec<(Type, Type)>>,
+}
+
+impl Database {
+    #[must_use]
+    /// The commands of the game
+    ///
+    /// # Panics
+    /// If the built in definitions are invalid
+    pub fn builtin() -> &'static Self {
+        static CELL: OnceLock<Database> = OnceLock::new();
+        CELL.get_or_init(|| {
+            let mut database = Self::default();
+            database
+                .extend(BUILTIN)
+                .expect("built in command definitions are valid");
+            database
+        })
+    }
+
+    /// The commands of the game, and the commands in `source`
+    ///
+    /// # Errors
+    /// [`Error::CommandDefinition`] if a line of `source` is not a valid definition
+    pub fn with_definitions(source: &str) -> Result<Self, Error> {
+        let mut database = Self::builtin().clone();
+        database.extend(source)?;
+        Ok(database)
+    }
+
+    fn extend(&mut self, source: &str) -> Result<(), Error> {
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+            let invalid = || Error::CommandDefinition(number + 1, line.to_string());
+            let (kind, definition) = line.split_once(':').ok_or_else(invalid)?;
+            let parts = definition.split_whitespace().collect::<Vec<_>>();
+            match (kind, parts.as_slice()) {
+                // `supportInfo` also lists the types
+                ("t", _) => {}
+                ("n", [name]) => {
+                    self.nular.insert(name.to_lowercase());
+                }
+                ("u", [name, argument]) => {
+                    self.unary
+                        .entry(name.to_lowercase())
+                        .or_default()
+                        .push(Type::from_name(argument));
+                }
+                ("b", [left, name, right]) => {
+                    self.binary
+                        .entry(name.to_lowercase())
+                        .or_default()
+                        .push((Type::from_name(left), Type::from_name(right)));
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    /// Check if a name is a known nular command
+    pub fn is_nular(&self, name: &str) -> bool {
+        self.nular.contains(&name.to_lowercase())
+    }
+
+    #[must_use]
+    /// Check if a name is a known unary command
+    pub fn is_unary(&self, name: &str) -> bool {
+        self.unary.contains_key(&name.to_lowercase())
+    }
+
+    #[must_use]
+    /// The precedence of a binary command, or `None` if it is not a binary command
+    pub fn binary_precedence(&self, name: &str) -> Option<Precedence> {
+        static CELL: OnceLock<HashMap<&str, Precedence>> = OnceLock::new();
+        let name = name.to_lowercase();
+        if !self.binary.contains_key(&name) {
+            return None;
+        }
+        Some(
+            CELL.get_or_init(|| PRECEDENCE.iter().copied().collect())
+                .get(name.as_str())
+                .copied()
+                .unwrap_or(Precedence::Binary),
+        )
+    }
+
+    #[must_use]
+    /// The argument types a unary command accepts
+    pub fn unary(&self, name: &str) -> Option<&[Type]> {
+        self.unary.get(&name.to_lowercase()).map(Vec::as_slice)
+    }
+
+    #[must_use]
+    /// The pairs of argument types a binary command accepts
+    pub fn binary(&self, name: &str) -> Option<&[(Type, Type)]> {
+        self.binary.get(&name.to_lowercase()).map(Vec::as_slice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Database, Precedence, Type};
+
+    #[test]
+    fn builtin() {
+        let database = Database::builtin();
+        assert!(database.is_nular("Player"));
+        assert!(database.is_unary("count"));
+        assert_eq!(
+            database.binary_precedence("SELECT"),
+            Some(Precedence::Binary)
+        );
+        assert_eq!(database.binary_precedence("&&"), Some(Precedence::And));
+        assert_eq!(database.binary_precedence("player"), None);
+        assert!(database.unary("count").unwrap().contains(&Type::Array));
+    }
+
+    #[test]
+    fn definitions() {
+        let database = Database::with_definitions(
+            "// extension\nn:ext_ready\nu:ext_call STRING\nb:OBJECT ext_attach ARRAY\nt:OBJECT",
+        )
+        .unwrap();
+        assert!(database.is_nular("ext_ready"));
+        assert_eq!(database.unary("ext_call"), Some([Type::String].as_slice()));
+        assert_eq!(
+            database.binary("EXT_attach"),
+            Some([(Type::Other("OBJECT".to_string()), Type::Array)].as_slice())
+        );
+        assert!(database.is_nular("player"));
+        assert!(Database::with_definitions("u:ext_call").is_err());
+    }
+}
@@ -0,0 +1,408 @@
+//! Compile SQF into the bytecode the game loads from `.sqfc` files
+
+mod serializer;
+
+use std::{collections::HashMap, ops::Range};
+
+use hemtt_common::reporting::Processed;
+
+use crate::{Expression, Statement, Statements};
+
+#[derive(Debug, Clone, PartialEq)]
+/// A compiled SQF file
+pub struct Compiled {
+    constants: Vec<Constant>,
+    names: Vec<String>,
+    locations: Vec<String>,
+    entry: usize,
+}
+
+impl Compiled {
+    #[must_use]
+    /// The constants, code blocks and literal values, used by the file
+    pub fn constants(&self) -> &[Constant] {
+        &self.constants
+    }
+
+    #[must_use]
+    /// The names of the commands and variables used by the file
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    #[must_use]
+    /// The paths of the files the instructions came from
+    pub fn locations(&self) -> &[String] {
+        &self.locations
+    }
+
+    #[must_use]
+    /// The index of the constant with the code of the whole file
+    pub const fn entry(&self) -> usize {
+        self.entry
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A constant value
+pub enum Constant {
+    /// A code block, with its source
+    Code {
+        /// The source of the code, without the braces
+        source: String,
+        /// The instructions of the code
+        instructions: Vec<Instruction>,
+    },
+    /// A string
+    String(String),
+    /// A number
+    Scalar(f32),
+    /// An array of constants
+    Array(Vec<Self>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A single instruction, and where it came from
+pub struct Instruction {
+    /// What the instruction does
+    pub kind: InstructionKind,
+    /// The offset in the original file
+    pub offset: usize,
+    /// The index of the original file in [`Compiled::locations`]
+    pub file: usize,
+    /// The line in the original file
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// What an instruction does, indexes are into [`Compiled::constants`] or [`Compiled::names`]
+pub enum InstructionKind {
+    /// Discard the value of the previous statement
+    EndStatement,
+    /// Push a constant
+    Push(usize),
+    /// Call a unary command
+    CallUnary(usize),
+    /// Call a binary command
+    CallBinary(usize),
+    /// Call a nular command
+    CallNular(usize),
+    /// Assign to a variable
+    AssignTo(usize),
+    /// Assign to a new variable in the current scope, `private _name = ...`
+    AssignToLocal(usize),
+    /// Get the value of a variable
+    GetVariable(usize),
+    /// Make an array from the given number of values
+    MakeArray(usize),
+}
+
+/// Compile a parsed SQF file
+///
+/// The positions of the instructions are taken from the mappings of `processed`,
+/// so errors in the game point to the original files. `location` gives the path of a
+/// workspace file as the game sees it, with the prefix of its addon.
+///
+/// Names that are not a command in the database were parsed as variables, and are
+/// compiled as variables, the same as the `ArmaScriptCompiler`.
+pub fn compile(
+    statements: &Statements,
+    processed: &Processed,
+    location: impl Fn(&str) -> String,
+) -> Compiled {
+    let mut compiler = Compiler {
+        processed,
+        location: &location,
+        constants: Vec::new(),
+        names: HashMap::new(),
+        locations: HashMap::new(),
+    };
+    let entry = compiler.code(statements, processed.as_string().to_string());
+    let mut names = compiler.names.into_iter().collect::<Vec<_>>();
+    names.sort_by_key(|(_, index)| *index);
+    let mut locations = compiler.locations.into_iter().collect::<Vec<_>>();
+    locations.sort_by_key(|(_, index)| *index);
+    Compiled {
+        constants: compiler.constants,
+        names: names.into_iter().map(|(name, _)| name).collect(),
+        locations: locations.into_iter().map(|(path, _)| path).collect(),
+        entry,
+    }
+}
+
+struct Compiler<'a> {
+    processed: &'a Processed,
+    location: &'a dyn Fn(&str) -> String,
+    constants: Vec<Constant>,
+    names: HashMap<String, usize>,
+    locations: HashMap<String, usize>,
+}
+
+impl Compiler<'_> {
+    fn constant(&mut self, constant: Constant) -> usize {
+        self.constants.push(constant);
+        self.constants.len() - 1
+    }
+
+    fn name(&mut self, name: String) -> usize {
+        let next = self.names.len();
+        *self.names.entry(name).or_insert(next)
+    }
+
+    fn instruction(&mut self, kind: InstructionKind, span: &Range<usize>) -> Instruction {
+        let Some(map) = self.processed.mapping(span.start) else {
+            return Instruction {
+                kind,
+                offset: span.start,
+                file: 0,
+                line: 0,
+            };
+        };
+        let next = self.locations.len();
+        let file = *self
+            .locations
+            .entry((self.location)(map.original().path().as_str()))
+            .or_insert(next);
+        Instruction {
+            kind,
+            offset: map.original().start().offset(),
+            file,
+            line: map.original().start().line(),
+        }
+    }
+
+    /// Compile a code block into a constant, returning its index
+    fn code(&mut self, statements: &Statements, source: String) -> usize {
+        let mut instructions = Vec::new();
+        for (i, statement) in statements.content().iter().enumerate() {
+            if i != 0 {
+                instructions
+                    .push(self.instruction(InstructionKind::EndStatement, &statement.span()));
+            }
+            self.statement(statement, &mut instructions);
+        }
+        self.constant(Constant::Code {
+            source,
+            instructions,
+        })
+    }
+
+    fn statement(&mut self, statement: &Statement, instructions: &mut Vec<Instruction>) {
+        match statement {
+            Statement::Assign {
+                name,
+                value,
+                private,
+                span,
+            } => {
+                self.expression(value, instructions);
+                let index = self.name(name.value.to_lowercase());
+                instructions.push(self.instruction(
+                    if *private {
+                        InstructionKind::AssignToLocal(index)
+                    } else {
+                        InstructionKind::AssignTo(index)
+                    },
+                    span,
+                ));
+            }
+            Statement::Expression(expression) => self.expression(expression, instructions),
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression, instructions: &mut Vec<Instruction>) {
+        // commands point to their name, so errors in the game point to the failing command
+        let mut span = expression.span();
+        let kind = match expression {
+            Expression::Code(statements) => {
+                let span = statements.span();
+                let source = self
+                    .processed
+                    .as_string()
+                    .get(span.start + 1..span.end - 1)
+                    .unwrap_or_default()
+                    .to_string();
+                InstructionKind::Push(self.code(statements, source))
+            }
+            Expression::Array(items, _) => {
+                if let Some(constant) = constant(expression) {
+                    InstructionKind::Push(self.constant(constant))
+                } else {
+                    for item in items {
+                        self.expression(item, instructions);
+                    }
+                    InstructionKind::MakeArray(items.len())
+                }
+            }
+            Expression::String(..) | Expression::Number(..) => InstructionKind::Push(
+                self.constant(constant(expression).expect("literals are constant")),
+            ),
+            Expression::Variable(ident) => {
+                InstructionKind::GetVariable(self.name(ident.value.to_lowercase()))
+            }
+            Expression::NularCommand(command) => {
+                InstructionKind::CallNular(self.name(command.value.to_lowercase()))
+            }
+            Expression::UnaryCommand(command, argument, _) => {
+                self.expression(argument, instructions);
+                span = command.span.clone();
+                InstructionKind::CallUnary(self.name(command.value.to_lowercase()))
+            }
+            Expression::BinaryCommand(command, left, right, _) => {
+                self.expression(left, instructions);
+                self.expression(right, instructions);
+                span = command.span.clone();
+                InstructionKind::CallBinary(self.name(command.value.to_lowercase()))
+            }
+        };
+        instructions.push(self.instruction(kind, &span));
+    }
+}
+
+/// The constant value of a literal, or an array of only literals
+fn constant(expression: &Expression) -> Option<Constant> {
+    match expression {
+        Expression::String(value, _) => Some(Constant::String(value.clone())),
+        Expression::Number(value, _) => Some(Constant::Scalar(*value)),
+        Expression::Array(items, _) => items
+            .iter()
+            .map(constant)
+            .collect::<Option<Vec<_>>>()
+            .map(Constant::Array),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_preprocessor::{NoIncludes, Processor};
+
+    use crate::commands::Database;
+
+    use super::{compile, Compiled, Constant, InstructionKind};
+
+    fn compiled(source: &str) -> Compiled {
+        let processed = Processor::run_str("/test.sqf", source, Vec::new(), NoIncludes).unwrap();
+        let statements =
+            crate::parse(&processed, Database::builtin()).unwrap_or_else(|_| panic!("{source}"));
+        compile(&statements, &processed, |path| format!("z/test{path}"))
+    }
+
+    /// The instructions of a code constant, with names and constants resolved
+    fn instructions(compiled: &Compiled, index: usize) -> Vec<String> {
+        let Constant::Code { instructions, .. } = &compiled.constants()[index] else {
+            panic!("not code");
+        };
+        instructions
+            .iter()
+            .map(|instruction| match instruction.kind {
+                InstructionKind::EndStatement => "end".to_string(),
+                InstructionKind::Push(index) => format!("push {:?}", compiled.constants()[index]),
+                InstructionKind::CallUnary(index) => format!("unary {}", compiled.names()[index]),
+                InstructionKind::CallBinary(index) => {
+                    format!("binary {}", compiled.names()[index])
+                }
+                InstructionKind::CallNular(index) => format!("nular {}", compiled.names()[index]),
+                InstructionKind::AssignTo(index) => format!("assign {}", compiled.names()[index]),
+                InstructionKind::AssignToLocal(index) => {
+                    format!("private {}", compiled.names()[index])
+                }
+                InstructionKind::GetVariable(index) => format!("get {}", compiled.names()[index]),
+                InstructionKind::MakeArray(count) => format!("array {count}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn statements() {
+        let compiled = compiled("private _a = 1 + 2;\nhint str _a;\nGLOBAL = [_a, \"b\"]");
+        assert_eq!(
+            instructions(&compiled, compiled.entry()),
+            vec![
+                "push Scalar(1.0)",
+                "push Scalar(2.0)",
+                "binary +",
+                "private _a",
+                "end",
+                "get _a",
+                "unary str",
+                "unary hint",
+                "end",
+                "get _a",
+                "push String(\"b\")",
+                "array 2",
+                "assign global",
+            ]
+        );
+        assert_eq!(compiled.locations(), ["z/test/test.sqf"]);
+    }
+
+    #[test]
+    fn code() {
+        let compiled = compiled("if (alive player) then { [1, [\"a\"]] } else { true }");
+        assert_eq!(
+            instructions(&compiled, compiled.entry()),
+            vec![
+                "nular player",
+                "unary alive",
+                "unary if",
+                "push Code { source: \" [1, [\\\"a\\\"]] \", instructions: [Instruction { kind: Push(0), offset: 25, file: 0, line: 1 }] }",
+                "push Code { source: \" true \", instructions: [Instruction { kind: CallNular(3), offset: 45, file: 0, line: 1 }] }",
+                "binary else",
+                "binary then",
+            ]
+        );
+        assert_eq!(
+            compiled.constants()[0],
+            Constant::Array(vec![
+                Constant::Scalar(1.0),
+                Constant::Array(vec![Constant::String("a".to_string())])
+            ])
+        );
+    }
+
+    #[test]
+    fn globals() {
+        let compiled = compiled("hint str [ace_player, GVAR_setting]");
+        assert_eq!(
+            instructions(&compiled, compiled.entry()),
+            vec![
+                "get ace_player",
+                "get gvar_setting",
+                "array 2",
+                "unary str",
+                "unary hint",
+            ]
+        );
+    }
+
+    #[test]
+    fn serialize() {
+        let compiled = compiled("hint \"a\"");
+        let mut output = Vec::new();
+        compiled.serialize(&mut output).unwrap();
+        assert_eq!(
+            output,
+            [
+                &1u32.to_le_bytes()[..],
+                // constants: "a", the file
+                &[0, 2, 0],
+                &[1, 1, 0, 0, 0, b'a'],
+                &[0, 8, 0, 0, 0],
+                b"hint \"a\"",
+                &[2, 0, 0, 0],
+                &[1, 5, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+                &[2, 0, 0, 0, 0, 0, 1, 0, 0, 0],
+                // locations
+                &[2, 1, 0, 15, 0, 0, 0],
+                b"z/test/test.sqf",
+                // entry point
+                &[3, 1, 0, 0, 0, 0, 0, 0, 0],
+                // names
+                &[5, 1, 0, 4, 0, 0, 0],
+                b"hint",
+            ]
+            .concat()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::{Error, ErrorKind, Write};
+
+use byteorder::{LittleEndian, WriteBytesExt};
+
+use super::{Compiled, Constant, Instruction, InstructionKind};
+
+/// The version of the format, the same as written by the `ArmaScriptCompiler`
+const VERSION: u32 = 1;
+
+/// The blocks of a compiled file, in the order of the format
+mod block {
+    pub const CONSTANT: u8 = 0;
+    pub const LOCATION_INFO: u8 = 2;
+    pub const CODE: u8 = 3;
+    pub const COMMAND_NAME_DIRECTORY: u8 = 5;
+}
+
+impl Compiled {
+    /// Write the compiled file in the `.sqfc` format
+    ///
+    /// # Errors
+    /// If the output fails, or the file has more constants, names or files than the format allows
+    pub fn serialize<O: Write>(&self, output: &mut O) -> Result<(), Error> {
+        output.write_u32::<LittleEndian>(VERSION)?;
+
+        output.write_u8(block::CONSTANT)?;
+        output.write_u16::<LittleEndian>(fit(self.constants.len())?)?;
+        for constant in &self.constants {
+            constant.serialize(output)?;
+        }
+
+        output.write_u8(block::LOCATION_INFO)?;
+        output.write_u16::<LittleEndian>(fit(self.locations.len())?)?;
+        for location in &self.locations {
+            write_string(output, location)?;
+        }
+
+        output.write_u8(block::CODE)?;
+        output.write_u64::<LittleEndian>(self.entry as u64)?;
+
+        output.write_u8(block::COMMAND_NAME_DIRECTORY)?;
+        output.write_u16::<LittleEndian>(fit(self.names.len())?)?;
+        for name in &self.names {
+            write_string(output, name)?;
+        }
+        Ok(())
+    }
+}
+
+impl Constant {
+    const fn code(&self) -> u8 {
+        match self {
+            Self::Code { .. } => 0,
+            Self::String(_) => 1,
+            Self::Scalar(_) => 2,
+            // 3 is a boolean, `true` and `false` are compiled as nular commands instead
+            Self::Array(_) => 4,
+        }
+    }
+
+    fn serialize<O: Write>(&self, output: &mut O) -> Result<(), Error> {
+        output.write_u8(self.code())?;
+        match self {
+            Self::Code {
+                source,
+                instructions,
+            } => {
+                write_string(output, source)?;
+                output.write_u32::<LittleEndian>(fit(instructions.len())?)?;
+                for instruction in instructions {
+                    instruction.serialize(output)?;
+                }
+            }
+            Self::String(value) => write_string(output, value)?,
+            Self::Scalar(value) => output.write_f32::<LittleEndian>(*value)?,
+            Self::Array(items) => {
+                output.write_u32::<LittleEndian>(fit(items.len())?)?;
+                for item in items {
+                    item.serialize(output)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Instruction {
+    fn serialize<O: Write>(&self, output: &mut O) -> Result<(), Error> {
+        let (code, argument) = match self.kind {
+            InstructionKind::EndStatement => (0, None),
+            InstructionKind::Push(index) => (1, Some(index)),
+            InstructionKind::CallUnary(index) => (2, Some(index)),
+            InstructionKind::CallBinary(index) => (3, Some(index)),
+            InstructionKind::CallNular(index) => (4, Some(index)),
+            InstructionKind::AssignTo(index) => (5, Some(index)),
+            InstructionKind::AssignToLocal(index) => (6, Some(index)),
+            InstructionKind::GetVariable(index) => (7, Some(index)),
+            InstructionKind::MakeArray(count) => (8, Some(count)),
+        };
+        output.write_u8(code)?;
+        output.write_u32::<LittleEndian>(fit(self.offset)?)?;
+        output.write_u8(fit(self.file)?)?;
+        output.write_u16::<LittleEndian>(fit(self.line)?)?;
+        match (self.kind, argument) {
+            (InstructionKind::Push(index), _) => output.write_u64::<LittleEndian>(index as u64)?,
+            (_, Some(argument)) => output.write_u16::<LittleEndian>(fit(argument)?)?,
+            (_, None) => {}
+        }
+        Ok(())
+    }
+}
+
+fn write_string<O: Write>(output: &mut O, value: &str) -> Result<(), Error> {
+    output.write_u32::<LittleEndian>(fit(value.len())?)?;
+    output.write_all(value.as_bytes())
+}
+
+/// Convert a length or index to the size used by the format
+fn fit<T: TryFrom<usize>>(value: usize) -> Result<T, Error> {
+    T::try_from(value).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("{value} is too large for the sqfc format"),
+        )
+    })
+}
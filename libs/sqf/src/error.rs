@@ -0,0 +1,12 @@
+use hemtt_common::error::thiserror;
+
+#[derive(thiserror::Error, Debug)]
+/// Error type for the SQF crate
+pub enum Error {
+    #[error("Invalid command definition on line {0}: `{1}`")]
+    /// A line of command definitions could not be read
+    CommandDefinition(usize, String),
+    #[error("Unable to format, unbalanced `{1}` on line {0}")]
+    /// A bracket is not closed, or closed by the wrong bracket
+    FormatUnbalanced(usize, char),
+}
@@ -0,0 +1,248 @@
+use crate::commands::Database;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// A command or variable name
+    Word,
+    Number,
+    String,
+    /// A macro call with its arguments, `NAME(...)`, kept as it was written
+    Macro,
+    Operator,
+    Open(char),
+    Close(char),
+    Comma,
+    Semicolon,
+    LineComment,
+    BlockComment,
+    /// A preprocessor directive, with any lines it continues onto
+    Directive,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Token<'a> {
+    pub kind: Kind,
+    pub text: &'a str,
+    /// The line the token starts on, from 1
+    pub line: usize,
+    /// Line breaks between the previous token and this one
+    pub newlines: usize,
+}
+
+/// Split unpreprocessed source into tokens, ignoring whitespace
+///
+/// Anything that is not known is kept as a single character [`Kind::Operator`],
+/// so no part of the source is lost
+#[allow(clippy::too_many_lines)]
+pub fn tokens<'a>(source: &'a str, database: &Database) -> Vec<Token<'a>> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let mut line = 1;
+    let mut newlines = 0;
+    let mut line_start = true;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if c.is_ascii_whitespace() {
+            if c == b'\n' {
+                line += 1;
+                newlines += 1;
+                line_start = true;
+            }
+            i += 1;
+            continue;
+        }
+        let start = i;
+        let next = bytes.get(i + 1).copied();
+        let kind = match c {
+            b'#' if line_start => {
+                // a backslash at the end of a line continues the directive
+                while i < bytes.len() {
+                    if bytes[i] == b'\n' {
+                        if source[start..i].trim_end().ends_with('\\') {
+                            line += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+                Kind::Directive
+            }
+            b'/' if next == Some(b'/') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+                Kind::LineComment
+            }
+            b'/' if next == Some(b'*') => {
+                i += 2;
+                while i < bytes.len() && !bytes[i..].starts_with(b"*/") {
+                    if bytes[i] == b'\n' {
+                        line += 1;
+                    }
+                    i += 1;
+                }
+                i = (i + 2).min(bytes.len());
+                Kind::BlockComment
+            }
+            b'"' | b'\'' => {
+                i = string(source, i, &mut line);
+                Kind::String
+            }
+            b'0'..=b'9' => {
+                i = number(bytes, i);
+                Kind::Number
+            }
+            b'.' if next.is_some_and(|c| c.is_ascii_digit()) => {
+                i = number(bytes, i);
+                Kind::Number
+            }
+            b'$' if next.is_some_and(|c| c.is_ascii_hexdigit()) => {
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_hexdigit() {
+                    i += 1;
+                }
+                Kind::Number
+            }
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                    i += 1;
+                }
+                if bytes.get(i) == Some(&b'(') && is_macro(&source[start..i], database) {
+                    i = arguments(source, i, &mut line);
+                    Kind::Macro
+                } else {
+                    Kind::Word
+                }
+            }
+            b'(' | b'[' | b'{' => {
+                i += 1;
+                Kind::Open(c as char)
+            }
+            b')' | b']' | b'}' => {
+                i += 1;
+                Kind::Close(c as char)
+            }
+            b',' => {
+                i += 1;
+                Kind::Comma
+            }
+            b';' => {
+                i += 1;
+                Kind::Semicolon
+            }
+            _ => {
+                i += if ["==", "!=", ">=", "<=", ">>", "&&", "||"]
+                    .iter()
+                    .any(|op| source[i..].starts_with(op))
+                {
+                    2
+                } else {
+                    source[i..].chars().next().map_or(1, char::len_utf8)
+                };
+                Kind::Operator
+            }
+        };
+        let text = source[start..i].trim_end();
+        tokens.push(Token {
+            kind,
+            text,
+            line: line - text.matches('\n').count(),
+            newlines,
+        });
+        newlines = 0;
+        line_start = false;
+    }
+    tokens
+}
+
+/// A name directly followed by `(` is a macro call, unless it is a command
+///
+/// Commands written in capitals, like `LOG(...)`, are more likely to be a macro
+fn is_macro(name: &str, database: &Database) -> bool {
+    if name.starts_with('_') {
+        return false;
+    }
+    if name.bytes().any(|c| c.is_ascii_uppercase()) && !name.bytes().any(|c| c.is_ascii_lowercase())
+    {
+        return true;
+    }
+    !(database.is_nular(name)
+        || database.is_unary(name)
+        || database.binary_precedence(name).is_some())
+}
+
+/// The end of a string starting at `start`, quotes are escaped by doubling them
+fn string(source: &str, start: usize, line: &mut usize) -> usize {
+    let bytes = source.as_bytes();
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if bytes[i] == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        if bytes[i] == b'\n' {
+            *line += 1;
+        }
+        i += 1;
+    }
+    i
+}
+
+fn number(bytes: &[u8], start: usize) -> usize {
+    let mut i = start;
+    if bytes[i] == b'0' && matches!(bytes.get(i + 1), Some(b'x' | b'X')) {
+        i += 2;
+        while i < bytes.len() && bytes[i].is_ascii_hexdigit() {
+            i += 1;
+        }
+        return i;
+    }
+    while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+        i += 1;
+    }
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        let mut exponent = i + 1;
+        if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+            exponent += 1;
+        }
+        if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+            i = exponent;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+        }
+    }
+    i
+}
+
+/// The end of the arguments of a macro call, `open` is the index of the `(`
+fn arguments(source: &str, open: usize, line: &mut usize) -> usize {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            b'"' | b'\'' => {
+                i = string(source, i, line);
+                continue;
+            }
+            b'\n' => *line += 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    i
+}
@@ -0,0 +1,377 @@
+//! # Format
+//!
+//! Formats SQF before it is preprocessed, so comments and preprocessor directives are kept.
+//! Macro calls are kept exactly as they were written, as their arguments are not SQF until
+//! they are expanded.
+//!
+//! - Code blocks, arrays and parentheses that span several lines are indented by four spaces,
+//!   the others are kept on one line
+//! - `{` is kept on the line of the command before it, and anything following a `}` on the
+//!   same line as it, such as `} else {` and `} forEach _units;`
+//! - In a code block that spans several lines each statement is on its own line,
+//!   in an array that spans several lines each element is on its own line
+//! - Binary commands and operators have a space on each side, unary operators are written
+//!   next to their argument
+//! - Other line breaks are kept, with at most one empty line in a row
+
+mod lexer;
+
+use lexer::{Kind, Token};
+
+use crate::{commands::Database, Error};
+
+const INDENT: &str = "    ";
+
+/// Format unpreprocessed SQF
+///
+/// Commands are told apart from variables and macros by the names in the `database`
+///
+/// # Errors
+/// [`Error::FormatUnbalanced`] if a bracket is not closed, or closed by the wrong bracket,
+/// usually because the brackets are opened in an `#ifdef`
+pub fn format(source: &str, database: &Database) -> Result<String, Error> {
+    let crlf = source.contains("\r\n");
+    let source = source.replace("\r\n", "\n");
+    let tokens = lexer::tokens(&source, database);
+    let multiline = multiline(&tokens)?;
+    let mut formatter = Formatter {
+        database,
+        out: String::new(),
+        groups: vec![Group {
+            bracket: '{',
+            multiline: true,
+            continuation: false,
+        }],
+        previous: None,
+        code: None,
+        before: None,
+        break_after: false,
+    };
+    for (i, token) in tokens.iter().enumerate() {
+        formatter.token(token, multiline[i]);
+    }
+    let mut out = formatter.out.trim_end().to_string();
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    if crlf {
+        out = out.replace('\n', "\r\n");
+    }
+    Ok(out)
+}
+
+/// If each opening bracket spans several lines, checking that every bracket is closed
+fn multiline(tokens: &[Token]) -> Result<Vec<bool>, Error> {
+    let mut multiline = vec![false; tokens.len()];
+    let mut open = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token.kind {
+            Kind::Open(_) => open.push(i),
+            Kind::Close(close) => {
+                let Some(start) = open.pop() else {
+                    return Err(Error::FormatUnbalanced(token.line, close));
+                };
+                let Kind::Open(bracket) = tokens[start].kind else {
+                    unreachable!("only opening brackets are pushed");
+                };
+                if matching(bracket) != close {
+                    return Err(Error::FormatUnbalanced(token.line, close));
+                }
+                // a line comment or directive is always followed by a line break
+                multiline[start] =
+                    i > start + 1 && tokens[start + 1..=i].iter().any(|inner| inner.newlines > 0);
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = open.pop() {
+        let Kind::Open(bracket) = tokens[start].kind else {
+            unreachable!("only opening brackets are pushed");
+        };
+        return Err(Error::FormatUnbalanced(tokens[start].line, bracket));
+    }
+    Ok(multiline)
+}
+
+const fn matching(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        _ => '}',
+    }
+}
+
+struct Group {
+    bracket: char,
+    multiline: bool,
+    /// If the current line continues a statement or element from the line before
+    continuation: bool,
+}
+
+struct Formatter<'a> {
+    database: &'a Database,
+    out: String,
+    /// The open brackets, the first is the file itself
+    groups: Vec<Group>,
+    /// The last token written
+    previous: Option<Token<'a>>,
+    /// The last token written that is not a comment or directive
+    code: Option<Token<'a>>,
+    /// The token written before [`Self::code`]
+    before: Option<Token<'a>>,
+    /// If the next token must start a new line, kept past comments on the same line
+    break_after: bool,
+}
+
+impl<'a> Formatter<'a> {
+    fn token(&mut self, token: &Token<'a>, multiline: bool) {
+        let comment = matches!(token.kind, Kind::LineComment | Kind::BlockComment);
+        let trailing_comment = comment && token.newlines == 0 && self.previous.is_some();
+        if let Kind::Close(_) = token.kind {
+            let group = self.groups.pop().expect("brackets are balanced");
+            if group.multiline {
+                self.break_after = true;
+            }
+        }
+        let group = self.groups.last().expect("the file is always a group");
+        let forced = (self.break_after && !trailing_comment) || token.kind == Kind::Directive;
+        let mut kept = if group.multiline && !self.joins(token) {
+            token.newlines.min(2)
+        } else {
+            0
+        };
+        if matches!(self.previous.map(|p| p.kind), Some(Kind::Open(_)))
+            || matches!(token.kind, Kind::Close(_))
+        {
+            kept = kept.min(1);
+        }
+        let breaks = if self.out.is_empty() {
+            0
+        } else if forced {
+            kept.max(1)
+        } else {
+            kept
+        };
+        if breaks > 0 {
+            // a line that starts in the middle of a statement or element is indented once more
+            if !matches!(token.kind, Kind::Close(_)) {
+                let continuation = !forced
+                    && !comment
+                    && group.bracket != '('
+                    && self.code.is_some_and(|code| match code.kind {
+                        Kind::Semicolon | Kind::Open(_) => false,
+                        Kind::Comma => group.bracket != '[',
+                        _ => true,
+                    });
+                self.groups
+                    .last_mut()
+                    .expect("the file is always a group")
+                    .continuation = continuation;
+            }
+            let trimmed = self.out.trim_end_matches(' ').len();
+            self.out.truncate(trimmed);
+            for _ in 0..breaks {
+                self.out.push('\n');
+            }
+            if token.kind != Kind::Directive {
+                let depth = self
+                    .groups
+                    .iter()
+                    .map(|g| usize::from(g.multiline) + usize::from(g.continuation))
+                    .sum::<usize>()
+                    - 1;
+                self.out.push_str(&INDENT.repeat(depth));
+            }
+        } else if !self.out.is_empty() && self.space(token) {
+            self.out.push(' ');
+        }
+        self.out.push_str(token.text);
+
+        if !trailing_comment || token.kind == Kind::LineComment {
+            self.break_after = false;
+        }
+        let group = self.groups.last_mut().expect("the file is always a group");
+        match token.kind {
+            Kind::Open(bracket) => {
+                self.groups.push(Group {
+                    bracket,
+                    multiline,
+                    continuation: false,
+                });
+                self.break_after = multiline;
+            }
+            Kind::Semicolon if group.bracket == '{' => {
+                group.continuation = false;
+                self.break_after = group.multiline;
+            }
+            Kind::Comma if group.bracket == '[' => {
+                group.continuation = false;
+                self.break_after = group.multiline;
+            }
+            Kind::LineComment | Kind::Directive => self.break_after = true,
+            _ => {}
+        }
+        self.previous = Some(*token);
+        if !comment && token.kind != Kind::Directive {
+            self.before = self.code.replace(*token);
+        }
+    }
+
+    /// If a line break before the token is removed, to keep it on the line before
+    fn joins(&self, token: &Token) -> bool {
+        if matches!(
+            self.previous.map(|p| p.kind),
+            None | Some(Kind::LineComment | Kind::Directive)
+        ) {
+            return false;
+        }
+        match token.kind {
+            Kind::Semicolon | Kind::Comma => true,
+            // `{` stays with the command before it, unless it starts a statement or element
+            Kind::Open('{') => !matches!(
+                self.previous.map(|p| p.kind),
+                Some(Kind::Semicolon | Kind::Comma | Kind::Open(_) | Kind::BlockComment)
+            ),
+            // `} else {` and `} forEach _list`
+            Kind::Word | Kind::Operator => {
+                matches!(self.previous.map(|p| p.kind), Some(Kind::Close('}')))
+            }
+            _ => false,
+        }
+    }
+
+    /// If a space is written between the last token and `token`, on the same line
+    fn space(&self, token: &Token) -> bool {
+        let Some(previous) = self.previous else {
+            return false;
+        };
+        match (previous.kind, token.kind) {
+            (_, Kind::Comma | Kind::Semicolon | Kind::Close(')' | ']'))
+            | (Kind::Open('(' | '['), _)
+            | (Kind::Open('{'), Kind::Close('}')) => false,
+            (_, Kind::Operator) if token.text == ":" => false,
+            (Kind::Operator, _) => !self.unary(),
+            _ => true,
+        }
+    }
+
+    /// If the last token is an operator that is used with only one argument
+    fn unary(&self) -> bool {
+        let Some(operator) = self.code.filter(|code| code.kind == Kind::Operator) else {
+            return false;
+        };
+        match operator.text {
+            "!" => true,
+            // binary if the token before is an argument
+            "-" | "+" => !self.before.is_some_and(|before| match before.kind {
+                Kind::Operator | Kind::Open(_) | Kind::Comma | Kind::Semicolon => false,
+                // a variable or nular command is an argument,
+                // unary and binary commands are followed by their argument
+                Kind::Word => {
+                    before.text.starts_with('_')
+                        || self.database.is_nular(before.text)
+                        || !(self.database.is_unary(before.text)
+                            || self.database.binary_precedence(before.text).is_some())
+                }
+                _ => true,
+            }),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::commands::Database;
+
+    fn format(source: &str) -> String {
+        let formatted = super::format(source, Database::builtin()).unwrap();
+        let texts = |source: &str| {
+            super::lexer::tokens(&source.replace("\r\n", "\n"), Database::builtin())
+                .iter()
+                .map(|token| token.text.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(source),
+            texts(&formatted),
+            "only whitespace should change"
+        );
+        assert_eq!(
+            super::format(&formatted, Database::builtin()).unwrap(),
+            formatted,
+            "formatting should not change formatted code"
+        );
+        formatted
+    }
+
+    #[test]
+    fn spacing() {
+        assert_eq!(
+            format("private _a=[1,2 ,-3]select(count _b-1);"),
+            "private _a = [1, 2, -3] select (count _b - 1);\n"
+        );
+        assert_eq!(
+            format("if(!_a&&{_b>=-1})then{hint\"x\"}else{_c=_d#0};"),
+            "if (!_a && { _b >= -1 }) then { hint \"x\" } else { _c = _d # 0 };\n"
+        );
+        assert_eq!(format("_x = abs -1 - 1e-5;"), "_x = abs -1 - 1e-5;\n");
+        assert_eq!(
+            format("switch (_a) do {case 1: {}; default {};};"),
+            "switch (_a) do { case 1: {}; default {}; };\n"
+        );
+    }
+
+    #[test]
+    fn blocks() {
+        assert_eq!(
+            format("if (_a) then\n{\n_b = 1; _c = 2;\n\n\n_d = 3;\n}\nelse {\n_e = [\n1,2];\n};"),
+            "if (_a) then {\n    _b = 1;\n    _c = 2;\n\n    _d = 3;\n} else {\n    _e = [\n        1,\n        2\n    ];\n};\n"
+        );
+        assert_eq!(
+            format("{\n_x call _fnc\n}\nforEach _units;"),
+            "{\n    _x call _fnc\n} forEach _units;\n"
+        );
+    }
+
+    #[test]
+    fn continuation() {
+        assert_eq!(
+            format("private _a = _b &&\n_c;\n_d = (\n_e ||\n_f\n);"),
+            "private _a = _b &&\n    _c;\n_d = (\n    _e ||\n    _f\n);\n"
+        );
+    }
+
+    #[test]
+    fn preserved() {
+        assert_eq!(
+            format(
+                "#include \"script_component.hpp\"\n/* a\n   b */\nif (_a) then { // why\n#ifdef DEBUG\nLOG_1(\"a  b\",_a);\n#endif\nGVAR(x)=QUOTE(a  +  b);\n};\n"
+            ),
+            "#include \"script_component.hpp\"\n/* a\n   b */\nif (_a) then { // why\n#ifdef DEBUG\n    LOG_1(\"a  b\",_a);\n#endif\n    GVAR(x) = QUOTE(a  +  b);\n};\n"
+        );
+        assert_eq!(
+            format("_a = 1; // one\n_b = 2;"),
+            "_a = 1; // one\n_b = 2;\n"
+        );
+        assert_eq!(format("_a = \"x\r\ny\";\r\n"), "_a = \"x\r\ny\";\r\n");
+    }
+
+    #[test]
+    fn unicode() {
+        assert_eq!(
+            format("/* Author: Jörg */\n_a=\"ö\"; // ü\n_b = 2 ×3;"),
+            "/* Author: Jörg */\n_a = \"ö\"; // ü\n_b = 2 × 3;\n"
+        );
+    }
+
+    #[test]
+    fn unbalanced() {
+        assert!(super::format(
+            "#ifdef A\nif (_a) then {\n#else\nif (_b) then {\n#endif\n};",
+            Database::builtin()
+        )
+        .is_err());
+        assert!(super::format("_a = [1, 2);", Database::builtin()).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+//! # Functions
+//!
+//! Functions declared and referenced with the CBA macros, `PREP(name)`, `FUNC(name)` and
+//! `EFUNC(component,name)`, found through the macros the preprocessor expanded
+
+use std::{collections::HashSet, sync::Arc};
+
+use hemtt_common::{
+    position::Position,
+    reporting::{Code, Output, Processed, Token},
+};
+
+use crate::codes::{sw10_unused_function::UnusedFunction, sw9_missing_function::MissingFunction};
+
+/// Macros that reference a function of the addon they are used in
+const FUNC: &[&str] = &["FUNC", "QFUNC", "QQFUNC", "DFUNC", "LINKFUNC"];
+/// Macros that reference a function of another addon, `EFUNC(component,name)`
+const EFUNC: &[&str] = &["EFUNC", "QEFUNC", "QQEFUNC", "DEFUNC"];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Function {
+    addon: String,
+    name: String,
+}
+
+impl Function {
+    fn new(addon: &str, name: &str) -> Self {
+        Self {
+            addon: addon.to_lowercase(),
+            name: name.to_lowercase(),
+        }
+    }
+}
+
+#[derive(Default)]
+/// Functions prepared and referenced across every file of a project
+///
+/// A function exists if it is prepared with `PREP(name)`, or its addon has a `fnc_name.sqf`
+pub struct Functions {
+    /// The addons of the project
+    addons: HashSet<String>,
+    /// Functions with a `fnc_name.sqf` file
+    files: HashSet<Function>,
+    /// Functions prepared with `PREP`, with the macro and if the warning is suppressed
+    prepared: Vec<(Function, Arc<Token>, bool)>,
+    /// References to functions, with the macro and the name as it was written
+    references: Vec<(Function, Arc<Token>, String, bool)>,
+}
+
+impl Functions {
+    #[must_use]
+    /// Create an empty set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an addon of the project, with the paths of the files in it
+    ///
+    /// Only functions of addons in the project are checked, other addons could be from any mod
+    pub fn add_addon<'a>(&mut self, addon: &str, files: impl IntoIterator<Item = &'a str>) {
+        self.addons.insert(addon.to_lowercase());
+        for file in files {
+            let file = file.rsplit('/').next().unwrap_or(file).to_lowercase();
+            if let Some(name) = file
+                .strip_prefix("fnc_")
+                .and_then(|name| name.strip_suffix(".sqf"))
+            {
+                self.files.insert(Function::new(addon, name));
+            }
+        }
+    }
+
+    /// Add the macros of a file that was preprocessed in `addon`
+    pub fn add(&mut self, addon: &str, processed: &Processed) {
+        self.walk(addon, processed, processed.output());
+    }
+
+    fn walk(&mut self, addon: &str, processed: &Processed, output: &[Output]) {
+        for output in output {
+            let Output::Macro(token, inner, expansion) = output else {
+                continue;
+            };
+            // `QUOTE(call FUNC(name))`, the arguments are expanded before the call
+            self.walk(addon, processed, &expansion.nested);
+            let arguments = expansion
+                .arguments
+                .iter()
+                .map(|argument| {
+                    argument
+                        .iter()
+                        .map(|token| token.symbol().to_string())
+                        .collect::<String>()
+                        .trim()
+                        .to_string()
+                })
+                .collect::<Vec<_>>();
+            let name = token.symbol().to_string();
+            match (name.as_str(), arguments.as_slice()) {
+                ("PREP", [function]) => {
+                    let suppressed = processed
+                        .is_suppressed(&UnusedFunction::new(token.clone(), function.clone()));
+                    self.prepared
+                        .push((Function::new(addon, function), token.clone(), suppressed));
+                }
+                (name, [function]) if FUNC.contains(&name) => {
+                    self.reference(addon, function, token, processed);
+                }
+                (name, [component, function]) if EFUNC.contains(&name) => {
+                    self.reference(component, function, token, processed);
+                }
+                // the function macros expand to each other, only the outermost is the reference
+                (name, _) if name == "PREP" || FUNC.contains(&name) || EFUNC.contains(&name) => {}
+                _ => self.walk(addon, processed, inner),
+            }
+        }
+    }
+
+    fn reference(
+        &mut self,
+        addon: &str,
+        function: &str,
+        token: &Arc<Token>,
+        processed: &Processed,
+    ) {
+        let suppressed = processed.is_suppressed(&MissingFunction::new(
+            token.clone(),
+            addon.to_string(),
+            function.to_string(),
+        ));
+        self.references.push((
+            Function::new(addon, function),
+            token.clone(),
+            function.to_string(),
+            suppressed,
+        ));
+    }
+
+    #[must_use]
+    /// [`MissingFunction`] warnings for references to functions of the project that do not exist
+    pub fn missing(&self) -> Vec<Box<dyn Code>> {
+        let prepared = self
+            .prepared
+            .iter()
+            .map(|(function, _, _)| function)
+            .collect::<HashSet<_>>();
+        let mut seen = HashSet::new();
+        self.references
+            .iter()
+            .filter(|(function, token, _, suppressed)| {
+                !suppressed
+                    && self.addons.contains(&function.addon)
+                    && !prepared.contains(function)
+                    && !self.files.contains(function)
+                    && seen.insert(token.position().clone())
+            })
+            .map(|(function, token, name, _)| {
+                Box::new(MissingFunction::new(
+                    token.clone(),
+                    function.addon.clone(),
+                    name.clone(),
+                )) as Box<dyn Code>
+            })
+            .collect()
+    }
+
+    #[must_use]
+    /// [`UnusedFunction`] warnings for functions that are prepared, but never referenced
+    pub fn unused(&self) -> Vec<Box<dyn Code>> {
+        let referenced = self
+            .references
+            .iter()
+            .map(|(function, _, _, _)| function)
+            .collect::<HashSet<_>>();
+        let suppressed = self
+            .prepared
+            .iter()
+            .filter(|(_, _, suppressed)| *suppressed)
+            .map(|(_, token, _)| token.position())
+            .collect::<HashSet<&Position>>();
+        let mut seen = HashSet::new();
+        self.prepared
+            .iter()
+            .filter(|(function, token, _)| {
+                !referenced.contains(function)
+                    && !suppressed.contains(token.position())
+                    && seen.insert(token.position().clone())
+            })
+            .map(|(function, token, _)| {
+                Box::new(UnusedFunction::new(token.clone(), function.name.clone())) as Box<dyn Code>
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hemtt_preprocessor::{NoIncludes, Processor};
+
+    use super::Functions;
+
+    const MACROS: &str = r"
+        #define DOUBLES(a,b) a##_##b
+        #define TRIPLES(a,b,c) a##_##b##_##c
+        #define QUOTE(a) #a
+        #define FUNC(name) TRIPLES(ADDON,fnc,name)
+        #define QFUNC(name) QUOTE(FUNC(name))
+        #define EFUNC(comp,name) TRIPLES(DOUBLES(PREFIX,comp),fnc,name)
+        #define PREP(name) FUNC(name) = compile preprocessFileLineNumbers QUOTE(fnc_##name.sqf)
+        #define PREFIX tst
+        #define ADDON DOUBLES(PREFIX,main)
+    ";
+
+    fn functions(addon: &str, source: &str, functions: &mut Functions) {
+        let processed = Processor::run_str(
+            &format!("/addons/{addon}/test.sqf"),
+            &format!("{MACROS}\n{source}"),
+            Vec::new(),
+            NoIncludes,
+        )
+        .unwrap();
+        functions.add(addon, &processed);
+    }
+
+    fn names(warnings: &[Box<dyn hemtt_common::reporting::Code>]) -> Vec<String> {
+        warnings.iter().map(|w| w.label_message()).collect()
+    }
+
+    #[test]
+    fn missing_and_unused() {
+        let mut project = Functions::new();
+        project.add_addon("main", ["/addons/main/functions/fnc_fromFile.sqf"]);
+        project.add_addon("other", Vec::new());
+        functions(
+            "main",
+            r"
+            PREP(used);
+            PREP(unused);
+            call FUNC(used);
+            call FUNC(fromFile);
+            call FUNC(missing);
+            call EFUNC(other,missing);
+            call EFUNC(external,anything);
+            QFUNC(missing)
+            ",
+            &mut project,
+        );
+        assert_eq!(
+            names(&project.missing()),
+            vec![
+                "no `fnc_missing.sqf` or `PREP(missing)` in `main`",
+                "no `fnc_missing.sqf` or `PREP(missing)` in `other`",
+                "no `fnc_missing.sqf` or `PREP(missing)` in `main`",
+            ]
+        );
+        assert_eq!(names(&project.unused()), vec!["`unused` is never used"]);
+    }
+
+    #[test]
+    fn config() {
+        let mut project = Functions::new();
+        project.add_addon("main", Vec::new());
+        functions("main", "PREP(init);", &mut project);
+        let processed = Processor::run_str(
+            "/addons/main/config.cpp",
+            &format!("{MACROS}\nclass Init {{ init = QUOTE(call FUNC(init)); }};"),
+            Vec::new(),
+            NoIncludes,
+        )
+        .unwrap();
+        project.add("main", &processed);
+        assert!(project.missing().is_empty());
+        assert!(project.unused().is_empty());
+    }
+
+    #[test]
+    fn suppressed() {
+        let mut project = Functions::new();
+        project.add_addon("main", Vec::new());
+        functions(
+            "main",
+            "#pragma hemtt suppress sw10\nPREP(api);\n#pragma hemtt suppress sw9\ncall FUNC(dynamic);",
+            &mut project,
+        );
+        assert!(project.missing().is_empty());
+        assert!(project.unused().is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+#![deny(clippy::all, clippy::nursery, missing_docs)]
+#![warn(clippy::pedantic)]
+
+//! HEMTT - SQF Parser
+//!
+//! Requires that files first be preprocessed by the `hemtt_preprocessor` crate.
+
+mod analyze;
+mod codes;
+pub mod commands;
+pub mod compiler;
+mod error;
+pub mod format;
+mod functions;
+mod model;
+mod parse;
+
+use chumsky::{Parser, Stream};
+use commands::Database;
+use hemtt_common::reporting::{Code, Processed};
+
+pub use analyze::analyze;
+pub use error::Error;
+pub use functions::Functions;
+pub use model::*;
+
+/// Parse a preprocessed SQF file
+///
+/// Spans in the returned statements are offsets into [`Processed::as_string`],
+/// commands are told apart from variables by the names in the `database`
+///
+/// # Errors
+/// If the file could not be split into tokens, or the tokens could not be parsed
+pub fn parse(processed: &Processed, database: &Database) -> Result<Statements, Vec<Box<dyn Code>>> {
+    let source = processed.as_string();
+    let end = source.len()..source.len() + 1;
+    let tokens = parse::lexer()
+        .parse(Stream::from_iter(
+            end.clone(),
+            source.char_indices().map(|(i, c)| (c, i..i + c.len_utf8())),
+        ))
+        .map_err(|errors| {
+            errors
+                .into_iter()
+                .map(|error| {
+                    Box::new(codes::se1_invalid_token::InvalidToken::new(&error)) as Box<dyn Code>
+                })
+                .collect::<Vec<_>>()
+        })?;
+    let (statements, errors) =
+        parse::statements(database).parse_recovery(Stream::from_iter(end, tokens.into_iter()));
+    match statements {
+        Some(statements) if errors.is_empty() => Ok(statements),
+        _ => Err(errors
+            .into_iter()
+            .map(|error| {
+                Box::new(codes::se2_unexpected_token::UnexpectedToken::new(&error)) as Box<dyn Code>
+            })
+            .collect()),
+    }
+}
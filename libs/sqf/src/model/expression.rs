@@ -0,0 +1,44 @@
+use std::ops::Range;
+
+use crate::{Ident, Statements};
+
+#[derive(Debug, Clone, PartialEq)]
+/// An expression
+pub enum Expression {
+    /// A code block, `{ ... }`
+    Code(Statements),
+    /// A string, with any `""` or `''` already unescaped
+    String(String, Range<usize>),
+    /// A number
+    Number(f32, Range<usize>),
+    /// An array, `[ ... ]`
+    Array(Vec<Self>, Range<usize>),
+    /// A local or global variable
+    Variable(Ident),
+    /// A nular command, `player`
+    NularCommand(Ident),
+    /// A unary command and its argument, `alive player`
+    ///
+    /// `!` and the sign operators `-` and `+` are unary commands too
+    UnaryCommand(Ident, Box<Self>, Range<usize>),
+    /// A binary command and its arguments, `_a select 1`
+    ///
+    /// Operators such as `+` and `==` are binary commands too
+    BinaryCommand(Ident, Box<Self>, Box<Self>, Range<usize>),
+}
+
+impl Expression {
+    #[must_use]
+    /// Get the span of the expression
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::Code(statements) => statements.span(),
+            Self::Variable(ident) | Self::NularCommand(ident) => ident.span.clone(),
+            Self::String(_, span)
+            | Self::Number(_, span)
+            | Self::Array(_, span)
+            | Self::UnaryCommand(_, _, span)
+            | Self::BinaryCommand(_, _, _, span) => span.clone(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// An identifier, the name of a variable or a command
+///
+/// ```sqf
+/// _my_ident = 1;
+/// ```
+///
+/// ```sqf
+/// hint "hello";
+/// ```
+pub struct Ident {
+    /// Identifier value
+    pub value: String,
+    /// Identifier span
+    pub span: Range<usize>,
+}
+
+impl Ident {
+    #[must_use]
+    /// Get the value of the identifier
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    #[must_use]
+    /// Is the identifier a local variable, starting with `_`
+    pub fn is_local(&self) -> bool {
+        self.value.starts_with('_')
+    }
+}
@@ -0,0 +1,7 @@
+mod expression;
+mod ident;
+mod statement;
+
+pub use expression::Expression;
+pub use ident::Ident;
+pub use statement::{Statement, Statements};
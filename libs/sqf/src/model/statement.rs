@@ -0,0 +1,59 @@
+use std::ops::Range;
+
+use crate::{Expression, Ident};
+
+#[derive(Debug, Clone, PartialEq)]
+/// A list of statements, the content of a file or a code block
+pub struct Statements {
+    pub(crate) content: Vec<Statement>,
+    pub(crate) span: Range<usize>,
+}
+
+impl Statements {
+    #[must_use]
+    /// Get the statements
+    pub fn content(&self) -> &[Statement] {
+        &self.content
+    }
+
+    #[must_use]
+    /// Get the span of the statements
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A statement, separated from the next by `;` or `,`
+pub enum Statement {
+    /// An assignment
+    ///
+    /// ```sqf
+    /// private _local = 1;
+    /// _local = 2;
+    /// GLOBAL = 3;
+    /// ```
+    Assign {
+        /// The variable being assigned
+        name: Ident,
+        /// The value assigned to it
+        value: Expression,
+        /// The assignment started with `private`
+        private: bool,
+        /// The span of the whole statement
+        span: Range<usize>,
+    },
+    /// An expression, the value of the last statement is the value of the code
+    Expression(Expression),
+}
+
+impl Statement {
+    #[must_use]
+    /// Get the span of the statement
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            Self::Assign { span, .. } => span.clone(),
+            Self::Expression(expression) => expression.span(),
+        }
+    }
+}
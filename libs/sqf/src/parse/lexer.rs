@@ -0,0 +1,154 @@
+use std::{fmt::Display, ops::Range};
+
+use chumsky::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// A token of SQF source
+pub enum Token {
+    /// A number, as written
+    Number(String),
+    /// A string, with any `""` or `''` already unescaped
+    String(String),
+    /// A variable or command name
+    Identifier(String),
+    /// An operator, `+`, `==`, `!`, ...
+    Operator(String),
+    /// `[`, `]`, `{`, `}`, `(`, `)`, `;` or `,`
+    Control(char),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(value) | Self::Identifier(value) | Self::Operator(value) => {
+                write!(f, "{value}")
+            }
+            Self::String(value) => write!(f, "\"{}\"", value.replace('"', "\"\"")),
+            Self::Control(c) => write!(f, "{c}"),
+        }
+    }
+}
+
+/// Split SQF source into tokens, with the span of each
+pub fn lexer() -> impl Parser<char, Vec<(Token, Range<usize>)>, Error = Simple<char>> {
+    let comment = just("//")
+        .then(take_until(text::newline().or(end())))
+        .ignored()
+        .or(just("/*").then(take_until(just("*/"))).ignored());
+    let skip = comment
+        .or(filter(|c: &char| c.is_whitespace()).ignored())
+        .repeated();
+
+    let token = choice((
+        number().map(Token::Number),
+        string('"').or(string('\'')).map(Token::String),
+        identifier().map(Token::Identifier),
+        choice((
+            just("==").to("=="),
+            just("!=").to("!="),
+            just(">=").to(">="),
+            just("<=").to("<="),
+            just(">>").to(">>"),
+            just("&&").to("&&"),
+            just("||").to("||"),
+        ))
+        .map(ToString::to_string)
+        .or(one_of("+-*/%^#!<>:=").map(String::from))
+        .map(Token::Operator),
+        one_of("[]{}();,").map(Token::Control),
+    ));
+
+    skip.clone()
+        .ignore_then(
+            token
+                .map_with_span(|token, span| (token, span))
+                .then_ignore(skip)
+                .repeated(),
+        )
+        .then_ignore(end())
+}
+
+fn number() -> impl Parser<char, String, Error = Simple<char>> {
+    let hex = one_of("0123456789abcdefABCDEF").repeated().at_least(1);
+    let digits = filter(char::is_ascii_digit).repeated().at_least(1);
+    let exponent = one_of("eE")
+        .chain(one_of("-+").or_not())
+        .chain::<char, _, _>(digits);
+    choice((
+        just('0')
+            .chain(one_of("xX"))
+            .chain::<char, _, _>(hex.clone())
+            .collect(),
+        just('$').chain(hex).collect(),
+        digits
+            .chain::<char, _, _>(just('.').chain(digits).or_not().flatten())
+            .or(just('.').chain(digits))
+            .chain::<char, _, _>(exponent.or_not().flatten())
+            .collect(),
+    ))
+}
+
+fn string(delimiter: char) -> impl Parser<char, String, Error = Simple<char>> {
+    just(delimiter)
+        .ignore_then(
+            just([delimiter; 2])
+                .to(delimiter)
+                .or(filter(move |c| *c != delimiter))
+                .repeated(),
+        )
+        .then(just(delimiter).or_not())
+        .try_map(|(content, end), span: Range<usize>| {
+            if end.is_some() {
+                Ok(content.into_iter().collect())
+            } else {
+                Err(Simple::custom(
+                    span.start..span.start + 1,
+                    "unclosed string",
+                ))
+            }
+        })
+}
+
+fn identifier() -> impl Parser<char, String, Error = Simple<char>> {
+    filter(|c: &char| c.is_ascii_alphabetic() || *c == '_')
+        .chain(filter(|c: &char| c.is_ascii_alphanumeric() || *c == '_').repeated())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::Parser;
+
+    use super::{lexer, Token};
+
+    #[test]
+    fn tokens() {
+        assert_eq!(
+            lexer().parse("_a = 0x1F + .5e2; // comment\nhint \"a \"\"b\"\"\";"),
+            Ok(vec![
+                (Token::Identifier("_a".to_string()), 0..2),
+                (Token::Operator("=".to_string()), 3..4),
+                (Token::Number("0x1F".to_string()), 5..9),
+                (Token::Operator("+".to_string()), 10..11),
+                (Token::Number(".5e2".to_string()), 12..16),
+                (Token::Control(';'), 16..17),
+                (Token::Identifier("hint".to_string()), 29..33),
+                (Token::String("a \"b\"".to_string()), 34..43),
+                (Token::Control(';'), 43..44),
+            ])
+        );
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(
+            lexer()
+                .parse("a >= b >> c == !d")
+                .unwrap()
+                .into_iter()
+                .map(|(token, _)| token.to_string())
+                .collect::<Vec<_>>(),
+            vec!["a", ">=", "b", ">>", "c", "==", "!", "d"]
+        );
+    }
+}
@@ -0,0 +1,422 @@
+//! # Parse
+
+#![allow(clippy::result_large_err)] // chumsky's errors are large
+
+use std::ops::Range;
+
+use chumsky::prelude::*;
+
+use crate::{
+    commands::{Database, Precedence},
+    Expression, Ident, Statement, Statements,
+};
+
+pub use self::lexer::{lexer, Token};
+
+mod lexer;
+
+/// The binary precedence groups, from the tightest to the loosest
+///
+/// `#` is handled separately, as it binds tighter than unary commands
+const BINARY_GROUPS: [Precedence; 8] = [
+    Precedence::Power,
+    Precedence::Multiply,
+    Precedence::Add,
+    Precedence::Else,
+    Precedence::Binary,
+    Precedence::Compare,
+    Precedence::And,
+    Precedence::Or,
+];
+
+/// Parse the tokens of an SQF file
+pub fn statements(
+    database: &Database,
+) -> impl Parser<Token, Statements, Error = Simple<Token>> + '_ {
+    recursive(|statements| {
+        let expression = expression(statements, database);
+        let assignment = ident()
+            .try_map(|ident, span| {
+                if ident.value.eq_ignore_ascii_case("private") {
+                    Ok(())
+                } else {
+                    Err(not_expected(span, ident))
+                }
+            })
+            .or_not()
+            .then(ident())
+            .then_ignore(just(Token::Operator("=".to_string())))
+            .then(expression.clone())
+            .map_with_span(|((private, name), value), span| Statement::Assign {
+                name,
+                value,
+                private: private.is_some(),
+                span,
+            });
+        let statement = assignment.or(expression.map(Statement::Expression));
+        let separator = just(Token::Control(';'))
+            .or(just(Token::Control(',')))
+            .repeated()
+            .at_least(1);
+        statement
+            .separated_by(separator)
+            .allow_leading()
+            .allow_trailing()
+            .map_with_span(|content, span| Statements { content, span })
+    })
+    .then_ignore(end())
+}
+
+fn expression<'a>(
+    statements: Recursive<'a, Token, Statements, Simple<Token>>,
+    database: &'a Database,
+) -> impl Parser<Token, Expression, Error = Simple<Token>> + Clone + 'a {
+    recursive(|expression| {
+        let parens = expression
+            .clone()
+            .delimited_by(just(Token::Control('(')), just(Token::Control(')')));
+        let atom = choice((
+            value(),
+            array(expression),
+            code(statements),
+            parens,
+            variable(database),
+        ));
+        let unary = unary(binary(atom, Precedence::Select, database), database);
+        BINARY_GROUPS
+            .into_iter()
+            .fold(unary.boxed(), |lower, group| {
+                binary(lower, group, database).boxed()
+            })
+    })
+}
+
+fn code(
+    statements: Recursive<'_, Token, Statements, Simple<Token>>,
+) -> impl Parser<Token, Expression, Error = Simple<Token>> + Clone + '_ {
+    statements
+        .delimited_by(just(Token::Control('{')), just(Token::Control('}')))
+        .map_with_span(|mut statements: Statements, span| {
+            statements.span = span;
+            Expression::Code(statements)
+        })
+        .recover_with(nested_delimiters(
+            Token::Control('{'),
+            Token::Control('}'),
+            [
+                (Token::Control('['), Token::Control(']')),
+                (Token::Control('('), Token::Control(')')),
+            ],
+            |span| {
+                Expression::Code(Statements {
+                    content: Vec::new(),
+                    span,
+                })
+            },
+        ))
+}
+
+fn array(
+    expression: Recursive<'_, Token, Expression, Simple<Token>>,
+) -> impl Parser<Token, Expression, Error = Simple<Token>> + Clone + '_ {
+    expression
+        .separated_by(just(Token::Control(',')))
+        .delimited_by(just(Token::Control('[')), just(Token::Control(']')))
+        .map_with_span(Expression::Array)
+        .recover_with(nested_delimiters(
+            Token::Control('['),
+            Token::Control(']'),
+            [
+                (Token::Control('{'), Token::Control('}')),
+                (Token::Control('('), Token::Control(')')),
+            ],
+            |span| Expression::Array(Vec::new(), span),
+        ))
+}
+
+/// A number or a string
+fn value() -> impl Parser<Token, Expression, Error = Simple<Token>> + Clone {
+    filter_map(|span: Range<usize>, token| match token {
+        Token::Number(number) => parse_number(&number).map_or_else(
+            || {
+                Err(Simple::custom(
+                    span.clone(),
+                    format!("invalid number `{number}`"),
+                ))
+            },
+            |value| Ok(Expression::Number(value, span.clone())),
+        ),
+        Token::String(value) => Ok(Expression::String(value, span)),
+        token => Err(Simple::expected_input_found(span, Vec::new(), Some(token))),
+    })
+}
+
+/// A variable or a nular command
+fn variable(
+    database: &Database,
+) -> impl Parser<Token, Expression, Error = Simple<Token>> + Clone + '_ {
+    ident()
+        .try_map(|ident, span| {
+            if ident.is_local()
+                || database.is_nular(&ident.value)
+                || database.binary_precedence(&ident.value).is_none()
+            {
+                Ok(ident)
+            } else {
+                Err(not_expected(span, ident))
+            }
+        })
+        .map(|ident| {
+            if !ident.is_local() && database.is_nular(&ident.value) {
+                Expression::NularCommand(ident)
+            } else {
+                Expression::Variable(ident)
+            }
+        })
+}
+
+/// Unary commands, which bind tighter than every binary command except `#`
+fn unary<'a, P>(
+    select: P,
+    database: &'a Database,
+) -> impl Parser<Token, Expression, Error = Simple<Token>> + Clone + 'a
+where
+    P: Parser<Token, Expression, Error = Simple<Token>> + Clone + 'a,
+{
+    recursive(|unary| {
+        let operator = filter_map(|span, token| match token {
+            Token::Operator(op) if op == "!" || op == "-" || op == "+" => {
+                Ok(Ident { value: op, span })
+            }
+            token => Err(Simple::expected_input_found(span, Vec::new(), Some(token))),
+        });
+        let known = ident().try_map(|ident, span| {
+            if !ident.is_local() && database.is_unary(&ident.value) {
+                Ok(ident)
+            } else {
+                Err(not_expected(span, ident))
+            }
+        });
+        // any other name is a unary command when it is followed by an argument
+        let implied = ident()
+            .try_map(|ident, span| {
+                if ident.is_local() || database.is_nular(&ident.value) {
+                    Err(not_expected(span, ident))
+                } else {
+                    Ok(ident)
+                }
+            })
+            .then_ignore(filter(|token| starts_argument(token, database)).rewind());
+        operator
+            .or(known)
+            .or(implied)
+            .then(unary)
+            .map_with_span(|(command, argument): (Ident, Expression), span| {
+                Expression::UnaryCommand(command, Box::new(argument), span)
+            })
+            .or(select)
+    })
+}
+
+fn ident() -> impl Parser<Token, Ident, Error = Simple<Token>> + Clone {
+    filter_map(|span, token| match token {
+        Token::Identifier(value) => Ok(Ident { value, span }),
+        token => Err(Simple::expected_input_found(span, Vec::new(), Some(token))),
+    })
+}
+
+/// An identifier that is not allowed where it was found
+fn not_expected(span: Range<usize>, ident: Ident) -> Simple<Token> {
+    Simple::expected_input_found(span, Vec::new(), Some(Token::Identifier(ident.value)))
+}
+
+/// Chain binary commands of one precedence group, left to right
+fn binary<'a, P>(
+    lower: P,
+    group: Precedence,
+    database: &'a Database,
+) -> impl Parser<Token, Expression, Error = Simple<Token>> + Clone + 'a
+where
+    P: Parser<Token, Expression, Error = Simple<Token>> + Clone + 'a,
+{
+    let command = filter_map(move |span, token| match token {
+        Token::Identifier(value) | Token::Operator(value)
+            if database.binary_precedence(&value) == Some(group) =>
+        {
+            Ok(Ident { value, span })
+        }
+        token => Err(Simple::expected_input_found(span, Vec::new(), Some(token))),
+    });
+    lower
+        .clone()
+        .then(command.then(lower).repeated())
+        .foldl(|left, (command, right)| {
+            let span = left.span().start..right.span().end;
+            Expression::BinaryCommand(command, Box::new(left), Box::new(right), span)
+        })
+}
+
+/// Can the token start the argument of a unary command, without being a binary command
+fn starts_argument(token: &Token, database: &Database) -> bool {
+    match token {
+        Token::Number(_) | Token::String(_) => true,
+        Token::Identifier(name) => database.binary_precedence(name).is_none(),
+        Token::Operator(op) => op == "!",
+        Token::Control(c) => matches!(c, '[' | '{' | '('),
+    }
+}
+
+/// Parse a decimal, `0x` or `$` hexadecimal number
+fn parse_number(number: &str) -> Option<f32> {
+    #[allow(clippy::cast_precision_loss)]
+    number
+        .strip_prefix("0x")
+        .or_else(|| number.strip_prefix("0X"))
+        .or_else(|| number.strip_prefix('$'))
+        .map_or_else(
+            || number.parse().ok(),
+            |hex| i64::from_str_radix(hex, 16).ok().map(|value| value as f32),
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::{Parser, Stream};
+
+    use crate::{commands::Database, Expression, Statement};
+
+    use super::{lexer, statements};
+
+    /// The statements as s-expressions, to check how they were grouped
+    fn tree(source: &str) -> Vec<String> {
+        fn expression(value: &Expression) -> String {
+            match value {
+                Expression::Code(code) => format!(
+                    "{{{}}}",
+                    code.content()
+                        .iter()
+                        .map(statement)
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                ),
+                Expression::String(value, _) => format!("{value:?}"),
+                Expression::Number(value, _) => value.to_string(),
+                Expression::Array(items, _) => format!(
+                    "[{}]",
+                    items.iter().map(expression).collect::<Vec<_>>().join(", ")
+                ),
+                Expression::Variable(ident) => ident.value.clone(),
+                Expression::NularCommand(ident) => format!("<{}>", ident.value),
+                Expression::UnaryCommand(command, argument, _) => {
+                    format!("({} {})", command.value, expression(argument))
+                }
+                Expression::BinaryCommand(command, left, right, _) => format!(
+                    "({} {} {})",
+                    command.value,
+                    expression(left),
+                    expression(right)
+                ),
+            }
+        }
+        fn statement(statement: &Statement) -> String {
+            match statement {
+                Statement::Assign {
+                    name,
+                    value,
+                    private,
+                    ..
+                } => format!(
+                    "{}{} = {}",
+                    if *private { "private " } else { "" },
+                    name.value,
+                    expression(value)
+                ),
+                Statement::Expression(value) => expression(value),
+            }
+        }
+        let tokens = lexer().parse(source).unwrap();
+        let len = source.len();
+        statements(Database::builtin())
+            .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+            .unwrap()
+            .content()
+            .iter()
+            .map(statement)
+            .collect()
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(tree("_a + _b * _c ^ 2"), vec!["(+ _a (* _b (^ _c 2)))"]);
+        assert_eq!(tree("_a - _b - _c"), vec!["(- (- _a _b) _c)"]);
+        assert_eq!(
+            tree("_a > 1 && _b || !_c"),
+            vec!["(|| (&& (> _a 1) _b) (! _c))"]
+        );
+        assert_eq!(
+            tree("configFile >> \"CfgVehicles\" >> _class"),
+            vec!["(>> (>> <configFile> \"CfgVehicles\") _class)"]
+        );
+    }
+
+    #[test]
+    fn unary() {
+        assert_eq!(
+            tree("hint str count _arr > 0"),
+            vec!["(> (hint (str (count _arr))) 0)"]
+        );
+        assert_eq!(tree("count _arr # 0"), vec!["(count (# _arr 0))"]);
+        assert_eq!(tree("-_a max 1"), vec!["(max (- _a) 1)"]);
+        assert_eq!(tree("myFunction _x"), vec!["(myFunction _x)"]);
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!(
+            tree("player setPos [0, 0.5, 1e2]"),
+            vec!["(setPos <player> [0, 0.5, 100])"]
+        );
+        assert_eq!(
+            tree("if (_a) then {1} else {2}"),
+            vec!["(then (if _a) (else {1} {2}))"]
+        );
+        assert_eq!(tree("[] call MY_fnc_test"), vec!["(call [] MY_fnc_test)"]);
+        assert_eq!(
+            tree("{ _x } forEach units group player"),
+            vec!["(forEach {_x} (units (group <player>)))"]
+        );
+        assert_eq!(
+            tree("_veh setTurretOpticsMode 1; _veh confirmSensorTarget [west, true]"),
+            vec![
+                "(setTurretOpticsMode _veh 1)",
+                "(confirmSensorTarget _veh [<west>, <true>])"
+            ]
+        );
+    }
+
+    #[test]
+    fn assignments() {
+        assert_eq!(
+            tree("private _a = 1;; _a = _a + 1, GLOBAL = 0x10;"),
+            vec!["private _a = 1", "_a = (+ _a 1)", "GLOBAL = 16"]
+        );
+        assert_eq!(
+            tree("private \"_b\"; private [\"_c\"]"),
+            vec!["(private \"_b\")", "(private [\"_c\"])"]
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for source in ["_a setPos;", "_a = ;", "[1, 2", "_a _b"] {
+            let tokens = lexer().parse(source).unwrap();
+            let len = source.len();
+            assert!(
+                statements(Database::builtin())
+                    .parse(Stream::from_iter(len..len + 1, tokens.into_iter()))
+                    .is_err(),
+                "{source}"
+            );
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use std::io::Read;
+
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::commands::Database;
+
+const ROOT: &str = "tests/errors/";
+
+macro_rules! bootstrap {
+    ($dir:ident) => {
+        paste::paste! {
+            #[test]
+            fn [<sqf_error_ $dir>]() {
+                check(stringify!($dir));
+            }
+        }
+    };
+}
+
+fn check(dir: &str) {
+    let folder = std::path::PathBuf::from(ROOT).join(dir);
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.sqf").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    let Err(errors) = hemtt_sqf::parse(&processed, Database::builtin()) else {
+        panic!("`{dir}` should not parse");
+    };
+    let mut expected = Vec::new();
+    std::fs::File::open(folder.join("stdout.ansi"))
+        .unwrap()
+        .read_to_end(&mut expected)
+        .unwrap();
+    let errors = errors
+        .iter()
+        .map(|e| e.report_generate_processed(&processed).unwrap())
+        .collect::<Vec<_>>();
+    if expected.is_empty() {
+        std::fs::write(
+            folder.join("stdout.ansi"),
+            errors.join("\n").replace('\r', "").as_bytes(),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        errors.join("\n").replace('\r', ""),
+        String::from_utf8(expected).unwrap().replace('\r', "")
+    );
+}
+
+bootstrap!(se1_invalid_token);
+bootstrap!(se2_missing_argument);
+bootstrap!(se2_unclosed_code);
@@ -0,0 +1,36 @@
+use hemtt_sqf::commands::Database;
+
+const ROOT: &str = "tests/format/";
+
+macro_rules! bootstrap {
+    ($dir:ident) => {
+        paste::paste! {
+            #[test]
+            fn [<sqf_format_ $dir>]() {
+                check(stringify!($dir));
+            }
+        }
+    };
+}
+
+fn check(dir: &str) {
+    let folder = std::path::PathBuf::from(ROOT).join(dir);
+    let source = std::fs::read_to_string(folder.join("source.sqf")).unwrap();
+    let formatted = hemtt_sqf::format::format(&source, Database::builtin()).unwrap();
+    let expected = folder.join("expected.sqf");
+    if !expected.exists() {
+        std::fs::write(&expected, &formatted).unwrap();
+    }
+    assert_eq!(
+        formatted.replace('\r', ""),
+        std::fs::read_to_string(expected).unwrap().replace('\r', "")
+    );
+    assert_eq!(
+        hemtt_sqf::format::format(&formatted, Database::builtin()).unwrap(),
+        formatted,
+        "formatting should not change formatted code"
+    );
+}
+
+bootstrap!(function);
+bootstrap!(hashmap);
@@ -0,0 +1,58 @@
+use std::io::Read;
+
+use hemtt_preprocessor::Processor;
+use hemtt_sqf::commands::Database;
+
+const ROOT: &str = "tests/warnings/";
+
+macro_rules! bootstrap {
+    ($dir:ident) => {
+        paste::paste! {
+            #[test]
+            fn [<sqf_warning_ $dir>]() {
+                check(stringify!($dir));
+            }
+        }
+    };
+}
+
+fn check(dir: &str) {
+    let folder = std::path::PathBuf::from(ROOT).join(dir);
+    let workspace = hemtt_common::workspace::Workspace::builder()
+        .physical(&folder)
+        .finish(None)
+        .unwrap();
+    let source = workspace.join("source.sqf").unwrap();
+    let processed = Processor::run(&source).unwrap();
+    let statements = hemtt_sqf::parse(&processed, Database::builtin())
+        .unwrap_or_else(|_| panic!("`{dir}` should parse"));
+    let mut expected = Vec::new();
+    std::fs::File::open(folder.join("stdout.ansi"))
+        .unwrap()
+        .read_to_end(&mut expected)
+        .unwrap();
+    let warnings = hemtt_sqf::analyze(&statements, Database::builtin())
+        .iter()
+        .map(|e| e.report_generate_processed(&processed).unwrap())
+        .collect::<Vec<_>>();
+    if expected.is_empty() {
+        std::fs::write(
+            folder.join("stdout.ansi"),
+            warnings.join("\n").replace('\r', "").as_bytes(),
+        )
+        .unwrap();
+    }
+    assert_eq!(
+        warnings.join("\n").replace('\r', ""),
+        String::from_utf8(expected).unwrap().replace('\r', "")
+    );
+}
+
+bootstrap!(sw1_undefined_variable);
+bootstrap!(sw2_unused_private);
+bootstrap!(sw3_assign_this);
+bootstrap!(sw4_shadowed_param);
+bootstrap!(sw5_private_in_loop);
+bootstrap!(sw6_unknown_command);
+bootstrap!(sw7_wrong_arity);
+bootstrap!(sw8_type_mismatch);